@@ -0,0 +1,162 @@
+use crate::error::{InternalError, Result};
+
+/// Where to find the cells which store the SMT root.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RootSource {
+    /// Only one cell in cell deps.
+    CellDep = 0x00,
+    /// One or more cells in cell deps, and all of them have the same root.
+    CellDeps,
+    /// Only one cell in inputs.
+    Input,
+}
+
+/// Where to find the proof in witnesses.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WitnessLocation {
+    /// The `lock` field of the `WitnessArgs` at the same index as the input.
+    Lock = 0x00,
+    /// The `input_type` field of the `WitnessArgs` at the same index as the
+    /// input.
+    InputType,
+    /// The `output_type` field of the `WitnessArgs` at the same index as the
+    /// input.
+    OutputType,
+    /// The first witness after all inputs, which is not a `WitnessArgs`, and
+    /// is shared by all inputs which use current script.
+    Extra,
+}
+
+/// What the value of the required key should be.
+pub(crate) enum Predicate {
+    /// The key has a value.
+    Present,
+    /// The key has no value.
+    Absent,
+    /// The blake2b hash of the value equals the hash.
+    Equals([u8; 32]),
+    /// The value is an `u64` number in little endian, and it is in the range
+    /// `[min, max]`.
+    InRange(u64, u64),
+}
+
+/// A key which should be proven, and the predicate of its value.
+pub(crate) struct Condition {
+    pub(crate) key_hash: [u8; 32],
+    pub(crate) predicate: Predicate,
+}
+
+/// Script args:
+/// - 32 bytes, the type script hash of the KV-store cell.
+/// - (optional) 1 byte, the source of the SMT root, default is `CellDep`.
+/// - (optional) 8 bytes, the max age of the SMT root in blocks, in little
+///   endian; zero means unlimited.
+/// - (optional) 1 byte, the location of the proof, default is `Lock`.
+/// - (optional) the condition:
+///   - 32 bytes, the blake2b hash of the required key.
+///   - 1 byte, the type of the predicate.
+///   - 0, 16 or 32 bytes, the parameters of the predicate.
+pub(crate) struct ScriptArgs {
+    pub(crate) type_hash: [u8; 32],
+    pub(crate) root_source: RootSource,
+    pub(crate) max_age: Option<u64>,
+    pub(crate) witness_location: WitnessLocation,
+    pub(crate) condition: Option<Condition>,
+}
+
+impl TryFrom<u8> for RootSource {
+    type Error = InternalError;
+    fn try_from(value: u8) -> core::result::Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(Self::CellDep),
+            0x01 => Ok(Self::CellDeps),
+            0x02 => Ok(Self::Input),
+            _ => Err(InternalError::InvalidRootSource),
+        }
+    }
+}
+
+impl TryFrom<u8> for WitnessLocation {
+    type Error = InternalError;
+    fn try_from(value: u8) -> core::result::Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(Self::Lock),
+            0x01 => Ok(Self::InputType),
+            0x02 => Ok(Self::OutputType),
+            0x03 => Ok(Self::Extra),
+            _ => Err(InternalError::InvalidWitnessLocation),
+        }
+    }
+}
+
+impl Condition {
+    fn from_slice(slice: &[u8]) -> Result<Self> {
+        if slice.len() < 33 {
+            return Err(InternalError::InvalidArgsLength.into());
+        }
+        let mut key_hash = [0u8; 32];
+        key_hash.copy_from_slice(&slice[..32]);
+        let params = &slice[33..];
+        let predicate = match (slice[32], params.len()) {
+            (0x00, 0) => Predicate::Present,
+            (0x01, 0) => Predicate::Absent,
+            (0x02, 32) => {
+                let mut hash = [0u8; 32];
+                hash.copy_from_slice(params);
+                Predicate::Equals(hash)
+            }
+            (0x03, 16) => {
+                let mut min = [0u8; 8];
+                let mut max = [0u8; 8];
+                min.copy_from_slice(&params[..8]);
+                max.copy_from_slice(&params[8..]);
+                Predicate::InRange(u64::from_le_bytes(min), u64::from_le_bytes(max))
+            }
+            (0x00..=0x03, _) => return Err(InternalError::InvalidArgsLength.into()),
+            _ => return Err(InternalError::InvalidPredicate.into()),
+        };
+        Ok(Self {
+            key_hash,
+            predicate,
+        })
+    }
+}
+
+impl ScriptArgs {
+    pub(crate) fn from_slice(slice: &[u8]) -> Result<Self> {
+        if !matches!(slice.len(), 32 | 33 | 41 | 42) && slice.len() < 42 + 33 {
+            return Err(InternalError::InvalidArgsLength.into());
+        }
+        let mut type_hash = [0u8; 32];
+        type_hash.copy_from_slice(&slice[..32]);
+        let root_source = if let Some(byte) = slice.get(32) {
+            RootSource::try_from(*byte)?
+        } else {
+            RootSource::CellDep
+        };
+        let max_age = if let Some(bytes) = slice.get(33..41) {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(bytes);
+            Some(u64::from_le_bytes(buf)).filter(|age| *age > 0)
+        } else {
+            None
+        };
+        let witness_location = if let Some(byte) = slice.get(41) {
+            WitnessLocation::try_from(*byte)?
+        } else {
+            WitnessLocation::Lock
+        };
+        let condition = if let Some(bytes) = slice.get(42..).filter(|bytes| !bytes.is_empty()) {
+            Some(Condition::from_slice(bytes)?)
+        } else {
+            None
+        };
+        Ok(Self {
+            type_hash,
+            root_source,
+            max_age,
+            witness_location,
+            condition,
+        })
+    }
+}
@@ -1,11 +1,15 @@
 use alloc::vec::Vec;
 
+use ckb_hash::blake2b_256;
 use ckb_smt_tool::types::{DataWithProofReader, H256};
 use ckb_std::{
     ckb_constants::Source, ckb_types::prelude::*, debug, high_level as hl, syscalls::SysError,
 };
 
-use crate::error::{InternalError, Result};
+use crate::{
+    args::{Condition, Predicate, RootSource, ScriptArgs, WitnessLocation},
+    error::{Error, InternalError, Result},
+};
 
 pub fn main() -> Result<()> {
     debug!("{} Starting ...", module_path!());
@@ -17,56 +21,125 @@ pub fn main() -> Result<()> {
     let script_args = script.args();
     let script_args_slice = script_args.as_reader().raw_data();
 
-    // Check the script args: args length.
-    if script_args_slice.len() != 32 {
-        return Err(InternalError::InvalidArgsLength.into());
-    }
-    let cell_dep_type_script_hash = &script_args_slice[..32];
+    // Check the script args.
+    let args = ScriptArgs::from_slice(script_args_slice)?;
 
-    debug!("find then load the SMT root from the cell dep");
-    // First 32 bytes is the type script hash for the KV-store cell.
-    let root = find_then_load_smt_root_from_cell_dep(cell_dep_type_script_hash)?;
+    debug!("find then load the SMT root");
+    let root = find_then_load_smt_root(&args)?;
 
     debug!("the SMT root is [{root:?}]");
-    check_witnesses(&script_hash, root)?;
+    check_witnesses(&script_hash, root, &args)?;
 
     debug!("{} DONE.", module_path!());
 
     Ok(())
 }
 
-fn find_then_load_smt_root_from_cell_dep(script_hash: &[u8]) -> Result<H256> {
-    let mut indexes = Vec::new();
-    for (index, type_hash_opt) in
-        hl::QueryIter::new(hl::load_cell_type_hash, Source::CellDep).enumerate()
-    {
-        if let Some(type_hash) = type_hash_opt {
-            if type_hash == script_hash {
-                if indexes.is_empty() {
-                    indexes.push(index);
-                } else {
-                    return Err(InternalError::CellDepMoreThanOne.into());
-                }
+fn find_then_load_smt_root(args: &ScriptArgs) -> Result<H256> {
+    let (source, indexes) = match args.root_source {
+        RootSource::CellDep => {
+            let indexes = find_cells(&args.type_hash, Source::CellDep);
+            if indexes.is_empty() {
+                return Err(InternalError::CellDepNotFound.into());
+            }
+            if indexes.len() > 1 {
+                return Err(InternalError::CellDepMoreThanOne.into());
+            }
+            (Source::CellDep, indexes)
+        }
+        RootSource::CellDeps => {
+            let indexes = find_cells(&args.type_hash, Source::CellDep);
+            if indexes.is_empty() {
+                return Err(InternalError::CellDepNotFound.into());
+            }
+            (Source::CellDep, indexes)
+        }
+        RootSource::Input => {
+            let indexes = find_cells(&args.type_hash, Source::Input);
+            if indexes.is_empty() {
+                return Err(InternalError::InputNotFound.into());
+            }
+            if indexes.len() > 1 {
+                return Err(InternalError::InputMoreThanOne.into());
             }
+            (Source::Input, indexes)
+        }
+    };
+
+    let root = load_smt_root(source, indexes[0])?;
+    for index in &indexes[1..] {
+        if load_smt_root(source, *index)? != root {
+            return Err(InternalError::CellDepRootIsMismatch.into());
         }
     }
-    if indexes.is_empty() {
-        return Err(InternalError::CellDepNotFound.into());
+
+    if let Some(max_age) = args.max_age {
+        debug!("check the age of the SMT root, at most {max_age} blocks");
+        check_root_age(source, &indexes, max_age)?;
     }
 
-    let cell_dep_index = indexes[0];
-    debug!("the SMT root is in the cell-deps[{cell_dep_index}]");
+    Ok(root)
+}
 
-    let cell_dep_data = hl::load_cell_data(cell_dep_index, Source::CellDep)?;
+fn find_cells(script_hash: &[u8], source: Source) -> Vec<usize> {
+    let mut indexes = Vec::new();
+    for (index, type_hash_opt) in hl::QueryIter::new(hl::load_cell_type_hash, source).enumerate() {
+        if let Some(type_hash) = type_hash_opt {
+            if type_hash == script_hash {
+                indexes.push(index);
+            }
+        }
+    }
+    indexes
+}
 
-    if cell_dep_data.len() != 32 {
-        return Err(InternalError::CellDepInvalidCellData.into());
+fn load_smt_root(source: Source, index: usize) -> Result<H256> {
+    debug!("the SMT root is in the {source:?}[{index}]");
+    let cell_data = hl::load_cell_data(index, source)?;
+    if cell_data.len() != 32 {
+        if source == Source::Input {
+            return Err(InternalError::InputInvalidCellData.into());
+        } else {
+            return Err(InternalError::CellDepInvalidCellData.into());
+        }
     }
+    Ok(checked_slice_to_h256(&cell_data))
+}
 
-    Ok(checked_slice_to_h256(&cell_dep_data))
+// The age of a cell is how many blocks it was committed before the latest
+// header in the header deps.
+fn check_root_age(source: Source, indexes: &[usize], max_age: u64) -> Result<()> {
+    let latest_number: u64 = hl::QueryIter::new(hl::load_header, Source::HeaderDep)
+        .map(|header| header.raw().number().unpack())
+        .max()
+        .ok_or(InternalError::HeaderDepNotFound)?;
+    debug!("the latest block number in header deps is {latest_number}");
+    for index in indexes {
+        let header = hl::load_header(*index, source).map_err(|err| match err {
+            SysError::ItemMissing => InternalError::HeaderDepNotFound.into(),
+            _ => Error::from(err),
+        })?;
+        let number: u64 = header.raw().number().unpack();
+        debug!("the SMT root in the {source:?}[{index}] is committed at block {number}");
+        if latest_number.saturating_sub(number) > max_age {
+            return Err(InternalError::RootIsTooOld.into());
+        }
+    }
+    Ok(())
 }
 
-fn check_witnesses(script_hash: &[u8], root: H256) -> Result<()> {
+fn check_witnesses(script_hash: &[u8], root: H256, args: &ScriptArgs) -> Result<()> {
+    let condition_opt = args.condition.as_ref();
+    let location = args.witness_location;
+    if location == WitnessLocation::Extra {
+        let inputs_count = hl::QueryIter::new(hl::load_input_since, Source::Input).count();
+        debug!("check the witness for all inputs, at index [{inputs_count}]");
+        let witness = hl::load_witness(inputs_count, Source::Input).map_err(|err| match err {
+            SysError::IndexOutOfBound => InternalError::WitnessIsNotExisted.into(),
+            _ => Error::from(err),
+        })?;
+        return verify_data_with_proof(&witness, &root, condition_opt);
+    }
     for (index, lock_hash) in hl::QueryIter::new(hl::load_cell_lock_hash, Source::Input).enumerate()
     {
         debug!("{index}-th lock hash of inputs: {:#x}", lock_hash.pack());
@@ -74,11 +147,14 @@ fn check_witnesses(script_hash: &[u8], root: H256) -> Result<()> {
             debug!("found cell: inputs[{index}]");
             let witness_args = hl::load_witness_args(index, Source::Input)?;
             debug!("check the witness args for index [{index}]");
-            if let Some(args) = witness_args.lock().to_opt() {
-                let data_with_proof_slice = &args.raw_data();
-                let data_with_proof = DataWithProofReader::from_slice(data_with_proof_slice)
-                    .map_err(|_| SysError::Encoding)?;
-                data_with_proof.verify_smt(&root)?;
+            let args_opt = match location {
+                WitnessLocation::Lock => witness_args.lock().to_opt(),
+                WitnessLocation::InputType => witness_args.input_type().to_opt(),
+                WitnessLocation::OutputType => witness_args.output_type().to_opt(),
+                WitnessLocation::Extra => unreachable!(),
+            };
+            if let Some(args) = args_opt {
+                verify_data_with_proof(&args.raw_data(), &root, condition_opt)?;
             } else {
                 return Err(InternalError::WitnessIsNotExisted.into());
             }
@@ -87,6 +163,51 @@ fn check_witnesses(script_hash: &[u8], root: H256) -> Result<()> {
     Ok(())
 }
 
+fn verify_data_with_proof(
+    data_with_proof_slice: &[u8],
+    root: &H256,
+    condition_opt: Option<&Condition>,
+) -> Result<()> {
+    let data_with_proof =
+        DataWithProofReader::from_slice(data_with_proof_slice).map_err(|_| SysError::Encoding)?;
+    data_with_proof.verify_smt(root)?;
+    if let Some(condition) = condition_opt {
+        check_condition(&data_with_proof, condition)?;
+    }
+    Ok(())
+}
+
+// All values of the required key in the proven data should satisfy the
+// predicate, and there should be at least one.
+fn check_condition(data_with_proof: &DataWithProofReader, condition: &Condition) -> Result<()> {
+    let mut is_proven = false;
+    for kv in data_with_proof.data().iter() {
+        if blake2b_256(kv.key().raw_data()) != condition.key_hash {
+            continue;
+        }
+        is_proven = true;
+        let value_opt = kv.value().to_opt().map(|value| value.raw_data());
+        let is_satisfied = match condition.predicate {
+            Predicate::Present => value_opt.is_some(),
+            Predicate::Absent => value_opt.is_none(),
+            Predicate::Equals(ref hash) => value_opt
+                .map(|value| blake2b_256(value) == *hash)
+                .unwrap_or(false),
+            Predicate::InRange(min, max) => value_opt
+                .and_then(|value| <[u8; 8]>::try_from(value).ok())
+                .map(|bytes| (min..=max).contains(&u64::from_le_bytes(bytes)))
+                .unwrap_or(false),
+        };
+        if !is_satisfied {
+            return Err(InternalError::PredicateIsNotSatisfied.into());
+        }
+    }
+    if !is_proven {
+        return Err(InternalError::KeyIsNotProven.into());
+    }
+    Ok(())
+}
+
 fn checked_slice_to_h256(slice: &[u8]) -> H256 {
     let mut v = [0u8; 32];
     v.copy_from_slice(slice);
@@ -20,6 +20,17 @@ pub enum InternalError {
     CellDepNotFound,
     CellDepInvalidCellData,
     WitnessIsNotExisted,
+    InvalidRootSource,
+    CellDepRootIsMismatch,
+    InputMoreThanOne,
+    InputNotFound,
+    InputInvalidCellData,
+    HeaderDepNotFound,
+    RootIsTooOld,
+    InvalidWitnessLocation,
+    InvalidPredicate,
+    KeyIsNotProven,
+    PredicateIsNotSatisfied,
 
     // This is not an error, just make sure the error code is less than 0x60.
     Unreachable = 0x60,
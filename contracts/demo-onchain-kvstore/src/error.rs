@@ -1,6 +1,6 @@
 use core::result;
 
-use ckb_smt_tool::error::UpdateError as SmtToolError;
+use ckb_smt_tool::error::{KvStoreError, UpdateError as SmtToolError};
 use ckb_std::error::SysError;
 
 pub type Result<T> = result::Result<T, Error>;
@@ -22,6 +22,7 @@ pub enum InternalError {
     CreateIncorrectUniqueId,
     CreateInitializedDataInvalidLength,
     CreateInitializedDataNotEmpty,
+    CreateNewRootIsMismatch,
 
     // 0x40 ~ 0x4f: Errors when update.
     UpdateInputDataInvalidLength = 0x40,
@@ -70,6 +71,34 @@ impl From<SmtToolError> for Error {
     }
 }
 
+impl From<KvStoreError> for Error {
+    fn from(err: KvStoreError) -> Self {
+        let internal = match err {
+            KvStoreError::Sys(e) => e.into(),
+            KvStoreError::Update(e) => return e.into(),
+            KvStoreError::UnknownOperation => InternalError::UnknownOperation,
+            KvStoreError::CreateInvalidArgsLength => InternalError::CreateInvalidArgsLength,
+            KvStoreError::CreateIncorrectUniqueId => InternalError::CreateIncorrectUniqueId,
+            KvStoreError::CreateInitializedDataInvalidLength => {
+                InternalError::CreateInitializedDataInvalidLength
+            }
+            KvStoreError::CreateInitializedDataNotEmpty => {
+                InternalError::CreateInitializedDataNotEmpty
+            }
+            KvStoreError::CreateNewRootIsMismatch => InternalError::CreateNewRootIsMismatch,
+            KvStoreError::UpdateInputDataInvalidLength => {
+                InternalError::UpdateInputDataInvalidLength
+            }
+            KvStoreError::UpdateOutputDataInvalidLength => {
+                InternalError::UpdateOutputDataInvalidLength
+            }
+            KvStoreError::UpdateWitnessIsNotExisted => InternalError::UpdateWitnessIsNotExisted,
+            KvStoreError::UpdateNewRootIsMismatch => InternalError::UpdateNewRootIsMismatch,
+        };
+        internal.into()
+    }
+}
+
 impl From<Error> for i8 {
     fn from(err: Error) -> Self {
         match err {
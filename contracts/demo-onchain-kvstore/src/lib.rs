@@ -0,0 +1,6 @@
+//! Errors of the contract, which are shared with the tests, so the tests could
+//! assert the exit codes by names.
+
+#![no_std]
+
+pub mod error;
@@ -8,8 +8,9 @@ extern crate alloc;
 use ckb_std::default_alloc;
 #[cfg(all(target_arch = "riscv64", not(test)))]
 ckb_std::entry!(program_entry);
+// A 2 MiB heap instead of the default 512 KiB one, to verify 1000 keys at once.
 #[cfg(all(target_arch = "riscv64", not(test)))]
-default_alloc!();
+default_alloc!(4 * 1024, 2052 * 1024, 64);
 
 // TODO ref: issue nervosnetwork/ckb-std#84
 #[cfg(target_arch = "riscv64")]
@@ -23,9 +24,7 @@ macro_rules! debug {
 #[cfg(target_arch = "riscv64")]
 mod entry;
 #[cfg(target_arch = "riscv64")]
-mod error;
-#[cfg(target_arch = "riscv64")]
-mod operations;
+use demo_onchain_kvstore::error;
 
 #[cfg(target_arch = "riscv64")]
 pub fn program_entry() -> i8 {
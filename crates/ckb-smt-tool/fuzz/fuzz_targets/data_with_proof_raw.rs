@@ -0,0 +1,23 @@
+//! Feeds arbitrary bytes into the verifier of `DataWithProof`.
+//!
+//! The first 32 bytes are used as the root, and the rest are parsed as a
+//! `DataWithProof`; the verifier should never panic.
+
+#![no_main]
+
+use ckb_smt_tool::types::{DataWithProofReader, H256};
+use libfuzzer_sys::fuzz_target;
+use molecule::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 {
+        return;
+    }
+    let (root, data) = data.split_at(32);
+    let mut root_bytes = [0u8; 32];
+    root_bytes.copy_from_slice(root);
+    let root: H256 = root_bytes.into();
+    if let Ok(data_with_proof) = DataWithProofReader::from_slice(data) {
+        let _ = data_with_proof.verify_smt(&root);
+    }
+});
@@ -0,0 +1,73 @@
+//! Builds an honest `DataWithProof` with the prover, then mutates its bytes.
+//!
+//! - The honest proof should always be accepted with the true root.
+//! - Any forged root should be rejected.
+//! - If a mutated proof is still accepted, all values in it must be same as
+//!   the values in the tree.
+
+#![no_main]
+
+use std::collections::BTreeSet;
+
+use arbitrary::Arbitrary;
+use ckb_smt_tool::types::{Bytes, DataWithProofReader, ProofGenerator, H256};
+use libfuzzer_sys::fuzz_target;
+use molecule::prelude::*;
+
+const MAX_ITEMS: usize = 32;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    initial: Vec<(Vec<u8>, Vec<u8>)>,
+    keys: BTreeSet<Vec<u8>>,
+    forged_root: [u8; 32],
+    mutations: Vec<(u16, u8)>,
+}
+
+fuzz_target!(|input: Input| {
+    if input.keys.is_empty() {
+        return;
+    }
+    let mut generator = ProofGenerator::new();
+    for (key, value) in input.initial.iter().take(MAX_ITEMS) {
+        let value = Some(Bytes::copy_from_slice(value));
+        generator.update(key, value).expect("smt update");
+    }
+    let root = *generator.root();
+    let keys = input
+        .keys
+        .iter()
+        .take(MAX_ITEMS)
+        .map(|key| Bytes::copy_from_slice(key))
+        .collect();
+    let data_with_proof = generator.data_with_proof(keys).expect("data with proof");
+    assert!(data_with_proof.as_reader().verify_smt(&root).is_ok());
+
+    let forged_root: H256 = input.forged_root.into();
+    if forged_root != root {
+        assert!(data_with_proof
+            .as_reader()
+            .verify_smt(&forged_root)
+            .is_err());
+    }
+
+    let mut data = data_with_proof.as_slice().to_vec();
+    for (index, xor) in input.mutations {
+        let index = usize::from(index) % data.len();
+        data[index] ^= xor;
+    }
+    let mutated = if let Ok(mutated) = DataWithProofReader::from_slice(&data) {
+        mutated
+    } else {
+        return;
+    };
+    if mutated.verify_smt(&root).is_err() {
+        return;
+    }
+    for kv in mutated.data().iter() {
+        let key = kv.key().raw_data();
+        let value = kv.value().to_opt().map(|v| v.raw_data().to_vec());
+        let stored = generator.get(key).expect("smt get").map(|v| v.to_vec());
+        assert_eq!(value, stored, "accepted a forged value");
+    }
+});
@@ -0,0 +1,23 @@
+//! Feeds arbitrary bytes into the verifier of `SmtUpdate`.
+//!
+//! The first 32 bytes are used as the old root, and the rest are parsed as an
+//! `SmtUpdate`; the verifier should never panic.
+
+#![no_main]
+
+use ckb_smt_tool::types::{SmtUpdateReader, H256};
+use libfuzzer_sys::fuzz_target;
+use molecule::prelude::*;
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 32 {
+        return;
+    }
+    let (root, data) = data.split_at(32);
+    let mut old_root = [0u8; 32];
+    old_root.copy_from_slice(root);
+    let old_root: H256 = old_root.into();
+    if let Ok(smt_update) = SmtUpdateReader::from_slice(data) {
+        let _ = smt_update.verify_smt(&old_root);
+    }
+});
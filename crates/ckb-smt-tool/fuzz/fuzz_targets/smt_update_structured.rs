@@ -0,0 +1,82 @@
+//! Builds an honest `SmtUpdate` with the prover, then mutates its bytes.
+//!
+//! - The honest update should always be accepted with the true old root.
+//! - Any forged old root should be rejected.
+//! - If a mutated update is still accepted, it must describe the same
+//!   transition as the tree does: all old values should be same as the
+//!   values in the tree, and applying all new values should produce the
+//!   claimed new root.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use ckb_smt_tool::types::{Bytes, ProofGenerator, SmtUpdateReader, H256};
+use libfuzzer_sys::fuzz_target;
+use molecule::prelude::*;
+
+const MAX_ITEMS: usize = 32;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    initial: Vec<(Vec<u8>, Vec<u8>)>,
+    changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+    forged_root: [u8; 32],
+    mutations: Vec<(u16, u8)>,
+}
+
+fn build_generator(initial: &[(Vec<u8>, Vec<u8>)]) -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for (key, value) in initial.iter().take(MAX_ITEMS) {
+        let value = Some(Bytes::copy_from_slice(value));
+        generator.update(key, value).expect("smt update");
+    }
+    generator
+}
+
+fuzz_target!(|input: Input| {
+    if input.changes.is_empty() {
+        return;
+    }
+    let mut generator = build_generator(&input.initial);
+    let old_root = *generator.root();
+    for (key, value) in input.changes.iter().take(MAX_ITEMS) {
+        let key = Bytes::copy_from_slice(key);
+        let value = value.as_deref().map(Bytes::copy_from_slice);
+        generator.append_change(key, value);
+    }
+    let smt_update = generator.commit_changes().expect("smt commit");
+    assert!(smt_update.as_reader().verify_smt(&old_root).is_ok());
+
+    let forged_root: H256 = input.forged_root.into();
+    if forged_root != old_root {
+        assert!(smt_update.as_reader().verify_smt(&forged_root).is_err());
+    }
+
+    let mut data = smt_update.as_slice().to_vec();
+    for (index, xor) in input.mutations {
+        let index = usize::from(index) % data.len();
+        data[index] ^= xor;
+    }
+    let mutated = if let Ok(mutated) = SmtUpdateReader::from_slice(&data) {
+        mutated
+    } else {
+        return;
+    };
+    if mutated.verify_smt(&old_root).is_err() {
+        return;
+    }
+
+    let mut replica = build_generator(&input.initial);
+    for change in mutated.changes().iter() {
+        let key = change.key().raw_data();
+        let old_value = change.old_value().to_opt().map(|v| v.raw_data().to_vec());
+        let stored = replica.get(key).expect("smt get").map(|v| v.to_vec());
+        assert_eq!(old_value, stored, "accepted a forged old value");
+    }
+    replica.apply_update(mutated).expect("apply update");
+    assert_eq!(
+        replica.root().as_slice(),
+        mutated.new_root().raw_data(),
+        "accepted a forged new root"
+    );
+});
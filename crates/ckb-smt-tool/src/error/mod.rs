@@ -1,9 +1,21 @@
 //! Errors.
 
+#[cfg(feature = "onchain")]
+pub(crate) mod onchain;
 #[cfg(feature = "with-prover")]
 pub(crate) mod prover;
+#[cfg(feature = "with-ckb-types")]
+pub(crate) mod tx_builder;
 pub(crate) mod verifier;
+#[cfg(feature = "with-ckb-types")]
+pub(crate) mod witness;
 
+#[cfg(feature = "onchain")]
+pub use onchain::KvStoreError;
 #[cfg(feature = "with-prover")]
 pub use prover::GeneratorError;
+#[cfg(feature = "with-ckb-types")]
+pub use tx_builder::TxBuilderError;
 pub use verifier::{UpdateError, VerifyError};
+#[cfg(feature = "with-ckb-types")]
+pub use witness::WitnessError;
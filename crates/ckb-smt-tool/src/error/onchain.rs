@@ -0,0 +1,40 @@
+//! Errors for the on-chain framework.
+
+use ckb_std::error::SysError;
+
+use crate::error::UpdateError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KvStoreError {
+    // Errors from syscalls, or from the verification of the update.
+    Sys(SysError),
+    Update(UpdateError),
+
+    // Errors before doing operations.
+    UnknownOperation,
+
+    // Errors when create.
+    CreateInvalidArgsLength,
+    CreateIncorrectUniqueId,
+    CreateInitializedDataInvalidLength,
+    CreateInitializedDataNotEmpty,
+    CreateNewRootIsMismatch,
+
+    // Errors when update.
+    UpdateInputDataInvalidLength,
+    UpdateOutputDataInvalidLength,
+    UpdateWitnessIsNotExisted,
+    UpdateNewRootIsMismatch,
+}
+
+impl From<SysError> for KvStoreError {
+    fn from(err: SysError) -> Self {
+        Self::Sys(err)
+    }
+}
+
+impl From<UpdateError> for KvStoreError {
+    fn from(err: UpdateError) -> Self {
+        Self::Update(err)
+    }
+}
@@ -1,16 +1,55 @@
 //! Errors for proof generation.
 
-use alloc::string::{String, ToString};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
 
 use thiserror::Error;
 
-use sparse_merkle_tree::error::Error as SmtLibError;
+use molecule::bytes::Bytes;
+use sparse_merkle_tree::{error::Error as SmtLibError, H256};
+
+use crate::error::{UpdateError, VerifyError};
 
 #[derive(Debug, Error)]
 pub enum GeneratorError {
     #[error("smt lib error: {0}")]
     SmtLib(#[from] SmtLibError),
 
+    #[error("the root {0:?} is not kept in the history")]
+    UnknownRoot(H256),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid snapshot: {0}")]
+    InvalidSnapshot(String),
+
+    #[error("the rebuilt root is {actual:?}, but the snapshot has {expected:?}")]
+    MismatchedSnapshotRoot { expected: H256, actual: H256 },
+
+    #[error("the data with proof at index {index} is invalid: {error:?}")]
+    InvalidDataWithProof { index: usize, error: VerifyError },
+
+    #[error("the update at index {index} is invalid: {error:?}")]
+    InvalidSmtUpdate { index: usize, error: UpdateError },
+
+    #[error("no key is changed by the updates in the end")]
+    NoNetChange,
+
+    #[error("the key {0:?} is not in the data with proof")]
+    KeyNotProven(Bytes),
+
+    #[error("the change of the key {0:?} exceeds the budget")]
+    ExceededBudget(Bytes),
+
+    #[error("the pending changes of the keys {0:?} conflict with the tree")]
+    Conflicts(Vec<Bytes>),
+
+    #[error("the foreign update is invalid: {0:?}")]
+    InvalidForeignUpdate(UpdateError),
+
     #[error("{0}")]
     Other(String),
 }
@@ -0,0 +1,20 @@
+//! Errors for building transactions.
+
+use thiserror::Error;
+
+use crate::{error::GeneratorError, types::H256};
+
+#[derive(Debug, Error)]
+pub enum TxBuilderError {
+    #[error("generator error: {0}")]
+    Generator(#[from] GeneratorError),
+
+    #[error("the initial tree should be empty, but the root is {0:?}")]
+    NonEmptyInitialTree(H256),
+
+    #[error("the data of the KV-store cell should be 32 bytes, but got {0} bytes")]
+    InvalidCellData(usize),
+
+    #[error("the root of the generator is {actual:?}, but the KV-store cell has {expected:?}")]
+    MismatchedRoot { expected: H256, actual: H256 },
+}
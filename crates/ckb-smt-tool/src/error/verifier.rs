@@ -1,15 +1,19 @@
 //! Errors for verification.
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i8)]
 pub enum UpdateError {
     ComputeOldRoot = 0x01,
     ComputeNewRoot,
     MismatchedOldRoot,
     MismatchedNewRoot,
+    EmptyChain,
+    DuplicatedKey,
     // This is not an error, just make sure the error code is less than 16.
     Unreachable = 0x10,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i8)]
 pub enum VerifyError {
     ComputeRoot = 0x01,
@@ -17,3 +21,29 @@ pub enum VerifyError {
     // This is not an error, just make sure the error code is less than 16.
     Unreachable = 0x10,
 }
+
+impl TryFrom<i8> for UpdateError {
+    type Error = i8;
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(Self::ComputeOldRoot),
+            0x02 => Ok(Self::ComputeNewRoot),
+            0x03 => Ok(Self::MismatchedOldRoot),
+            0x04 => Ok(Self::MismatchedNewRoot),
+            0x05 => Ok(Self::EmptyChain),
+            0x06 => Ok(Self::DuplicatedKey),
+            _ => Err(value),
+        }
+    }
+}
+
+impl TryFrom<i8> for VerifyError {
+    type Error = i8;
+    fn try_from(value: i8) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(Self::ComputeRoot),
+            0x02 => Ok(Self::MismatchedRoot),
+            _ => Err(value),
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Errors for witnesses.
+
+use molecule::error::VerificationError;
+use thiserror::Error;
+
+use crate::witness::WitnessField;
+
+#[derive(Debug, Error)]
+pub enum WitnessError {
+    #[error("the witness at index {0} is not existed")]
+    NotExisted(usize),
+
+    #[error("the witness at index {index} is not a WitnessArgs: {source}")]
+    InvalidWitnessArgs {
+        index: usize,
+        source: VerificationError,
+    },
+
+    #[error("the {field} field of the witness at index {index} is empty")]
+    EmptyField { index: usize, field: WitnessField },
+
+    #[error("the {field} field of the witness at index {index} is invalid: {source}")]
+    InvalidField {
+        index: usize,
+        field: WitnessField,
+        source: VerificationError,
+    },
+}
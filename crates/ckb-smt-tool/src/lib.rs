@@ -8,4 +8,14 @@ extern crate alloc;
 extern crate std;
 
 pub mod error;
+#[cfg(feature = "onchain")]
+pub mod onchain;
+#[cfg(feature = "with-ckb-types")]
+pub mod tx_builder;
 pub mod types;
+pub mod unique_id;
+#[cfg(feature = "with-ckb-types")]
+pub mod witness;
+
+#[cfg(test)]
+mod tests;
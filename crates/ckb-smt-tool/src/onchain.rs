@@ -0,0 +1,212 @@
+//! A framework for type scripts of KV-store cells.
+//!
+//! Each KV-store cell stores a 32 bytes SMT root in its data, and it's
+//! identified by its type script, whose args is the unique ID of the cell.
+//!
+//! [`run`] locates the cells of the current script in inputs and outputs,
+//! checks the common rules of the operation, then dispatches it to the hooks:
+//!
+//! - Create: one cell in outputs. The args should be the unique ID; the data
+//!   should be the zero root, or the new root of the `SmtUpdate` in the
+//!   `output_type` of the witness at the same index, which is verified from
+//!   the zero root.
+//!
+//! - Update: one cell in inputs and one cell in outputs. The `SmtUpdate` in
+//!   the `output_type` of the witness at the index of the output is verified
+//!   from the root in the input, and its new root should be the root in the
+//!   output.
+//!
+//! - Destroy: one cell in inputs.
+
+use alloc::vec::Vec;
+
+use ckb_std::{ckb_constants::Source, ckb_types::prelude::*, error::SysError, high_level as hl};
+
+use crate::{
+    error::KvStoreError,
+    types::{SmtUpdateReader, H256},
+    unique_id::{load_then_calculate_unique_id, UNIQUE_ID_LEN},
+};
+
+/// A created KV-store cell.
+pub struct Create<'a> {
+    /// The index of the cell in outputs.
+    pub output_index: usize,
+    /// The initial root.
+    pub root: H256,
+    /// The initial content, which is already verified; `None` if the tree is
+    /// empty.
+    pub update: Option<SmtUpdateReader<'a>>,
+}
+
+/// An updated KV-store cell.
+pub struct Update<'a> {
+    /// The index of the cell in inputs.
+    pub input_index: usize,
+    /// The index of the cell in outputs.
+    pub output_index: usize,
+    /// The root in the input.
+    pub old_root: H256,
+    /// The root in the output.
+    pub new_root: H256,
+    /// The update, which is already verified.
+    pub update: SmtUpdateReader<'a>,
+}
+
+/// A destroyed KV-store cell.
+pub struct Destroy {
+    /// The index of the cell in inputs.
+    pub input_index: usize,
+}
+
+/// The hooks which are called after the common rules are checked; all hooks
+/// accept the operation by default.
+///
+/// [`run`] calls exactly one hook for each execution of the script, and
+/// returns its result as is; so an `Err` from a hook rejects the transaction,
+/// and the script exits with the code which the contract maps the error to,
+/// as for the errors of the common rules.
+pub trait KvStoreHooks {
+    /// The error of the contract; the errors of the common rules are
+    /// converted into it by `From<KvStoreError>`.
+    type Error: From<KvStoreError>;
+
+    /// Called when a KV-store cell is created, after its args are checked as
+    /// the unique ID and its initial content is verified from the zero root.
+    ///
+    /// Returns an `Err` to reject the initial content.
+    fn create(&mut self, _cell: &Create<'_>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called when a KV-store cell is updated, after the update is verified
+    /// from the root in the input to the root in the output.
+    ///
+    /// Returns an `Err` to reject the changes, for example, a key which is
+    /// not allowed to be changed.
+    fn update(&mut self, _cell: &Update<'_>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Called when a KV-store cell is destroyed; nothing is checked by the
+    /// common rules.
+    ///
+    /// Returns an `Err` to keep the cell alive.
+    fn destroy(&mut self, _cell: &Destroy) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Checks the operation of the current script, then dispatches it to the
+/// hooks.
+pub fn run<H: KvStoreHooks>(hooks: &mut H) -> Result<(), H::Error> {
+    let script_hash = hl::load_script_hash().map_err(KvStoreError::from)?;
+    let indexes_of_inputs = find_cells(&script_hash, Source::Input);
+    let indexes_of_outputs = find_cells(&script_hash, Source::Output);
+
+    match (indexes_of_inputs.as_slice(), indexes_of_outputs.as_slice()) {
+        ([], [output_index]) => create(hooks, *output_index),
+        ([input_index], []) => hooks.destroy(&Destroy {
+            input_index: *input_index,
+        }),
+        ([input_index], [output_index]) => update(hooks, *input_index, *output_index),
+        _ => Err(KvStoreError::UnknownOperation.into()),
+    }
+}
+
+// Finds the indexes of all cells which use the script as their type.
+fn find_cells(script_hash: &[u8; 32], source: Source) -> Vec<usize> {
+    hl::QueryIter::new(hl::load_cell_type_hash, source)
+        .enumerate()
+        .filter(|(_, type_hash_opt)| type_hash_opt.as_ref() == Some(script_hash))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn create<H: KvStoreHooks>(hooks: &mut H, output_index: usize) -> Result<(), H::Error> {
+    let script = hl::load_script().map_err(KvStoreError::from)?;
+    let script_args = script.args();
+    let script_args_slice = script_args.as_reader().raw_data();
+    if script_args_slice.len() != UNIQUE_ID_LEN {
+        return Err(KvStoreError::CreateInvalidArgsLength.into());
+    }
+    let unique_id = load_then_calculate_unique_id(output_index).map_err(KvStoreError::from)?;
+    if unique_id != script_args_slice {
+        return Err(KvStoreError::CreateIncorrectUniqueId.into());
+    }
+
+    let root = load_root(output_index, Source::Output)?
+        .ok_or(KvStoreError::CreateInitializedDataInvalidLength)?;
+
+    let witness_args_opt = match hl::load_witness_args(output_index, Source::Output) {
+        Ok(witness_args) => Some(witness_args),
+        Err(SysError::IndexOutOfBound) => None,
+        Err(err) => return Err(KvStoreError::from(err).into()),
+    };
+    if let Some(args) = witness_args_opt.and_then(|wa| wa.output_type().to_opt()) {
+        let update_data = args.raw_data();
+        let update = load_update(&update_data)?;
+        if update.new_root().as_slice() != root.as_slice() {
+            return Err(KvStoreError::CreateNewRootIsMismatch.into());
+        }
+        update
+            .verify_smt(&H256::zero())
+            .map_err(KvStoreError::from)?;
+        hooks.create(&Create {
+            output_index,
+            root,
+            update: Some(update),
+        })
+    } else if !root.is_zero() {
+        Err(KvStoreError::CreateInitializedDataNotEmpty.into())
+    } else {
+        hooks.create(&Create {
+            output_index,
+            root,
+            update: None,
+        })
+    }
+}
+
+fn update<H: KvStoreHooks>(
+    hooks: &mut H,
+    input_index: usize,
+    output_index: usize,
+) -> Result<(), H::Error> {
+    let old_root =
+        load_root(input_index, Source::Input)?.ok_or(KvStoreError::UpdateInputDataInvalidLength)?;
+    let new_root = load_root(output_index, Source::Output)?
+        .ok_or(KvStoreError::UpdateOutputDataInvalidLength)?;
+
+    let witness_args =
+        hl::load_witness_args(output_index, Source::Output).map_err(KvStoreError::from)?;
+    let args = witness_args
+        .output_type()
+        .to_opt()
+        .ok_or(KvStoreError::UpdateWitnessIsNotExisted)?;
+    let update_data = args.raw_data();
+    let update = load_update(&update_data)?;
+    if update.new_root().as_slice() != new_root.as_slice() {
+        return Err(KvStoreError::UpdateNewRootIsMismatch.into());
+    }
+    update.verify_smt(&old_root).map_err(KvStoreError::from)?;
+    hooks.update(&Update {
+        input_index,
+        output_index,
+        old_root,
+        new_root,
+        update,
+    })
+}
+
+// Loads the root from the data of a cell; returns `None` if the data is not
+// 32 bytes.
+fn load_root(index: usize, source: Source) -> Result<Option<H256>, KvStoreError> {
+    let data = hl::load_cell_data(index, source)?;
+    let root = <[u8; 32]>::try_from(data.as_slice()).ok().map(Into::into);
+    Ok(root)
+}
+
+fn load_update(data: &[u8]) -> Result<SmtUpdateReader<'_>, KvStoreError> {
+    SmtUpdateReader::from_slice(data).map_err(|_| KvStoreError::Sys(SysError::Encoding))
+}
@@ -0,0 +1,99 @@
+use alloc::vec::Vec;
+
+use super::{build_generator, key, value};
+use crate::{
+    error::GeneratorError,
+    types::{verifier::hash_to_h256, CommitBudget, ProofGenerator, SmtUpdate, H256},
+};
+
+// Builds a tree with some leaves, then appends changes which update, insert
+// and remove leaves.
+fn prepare() -> ProofGenerator {
+    let mut generator = build_generator(100);
+    for i in 50..150 {
+        generator.append_change(key(i), value(i, 1));
+    }
+    for i in 0..10 {
+        generator.append_change(key(i), None);
+    }
+    generator
+}
+
+// Checks that the updates chain from the old root to the root of the
+// generator, and each of them is within the budget.
+fn check_updates(old_root: &H256, updates: &[SmtUpdate], budget: CommitBudget) {
+    let mut root = *old_root;
+    for update in updates {
+        let reader = update.as_reader();
+        assert!(budget.allows(reader));
+        assert_eq!(reader.verify_smt(&root), Ok(()));
+        root = hash_to_h256(reader.new_root().raw_data());
+    }
+}
+
+#[test]
+fn split_by_size() {
+    let mut expected = prepare();
+    expected.commit_changes().expect("commit changes");
+
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    let budget = CommitBudget::size(2048);
+    let updates = generator.commit_changes_within(budget).expect("commit");
+    assert!(updates.len() > 1);
+    check_updates(&old_root, &updates, budget);
+    assert_eq!(generator.root(), expected.root());
+    assert!(!generator.has_pending_changes());
+
+    let changes_count: usize = updates.iter().map(|u| u.changes().len()).sum();
+    assert_eq!(changes_count, 110);
+    let keys: Vec<_> = generator.keys().cloned().collect();
+    let expected_keys: Vec<_> = expected.keys().cloned().collect();
+    assert_eq!(keys, expected_keys);
+}
+
+#[test]
+fn split_by_cycles() {
+    let mut expected = prepare();
+    expected.commit_changes().expect("commit changes");
+
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    let budget = CommitBudget {
+        max_size: Some(16 * 1024),
+        max_cycles: Some(2_000_000),
+    };
+    let updates = generator.commit_changes_within(budget).expect("commit");
+    assert!(updates.len() > 1);
+    check_updates(&old_root, &updates, budget);
+    assert_eq!(generator.root(), expected.root());
+}
+
+#[test]
+fn unlimited_budget() {
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    let updates = generator
+        .commit_changes_within(CommitBudget::default())
+        .expect("commit");
+    assert_eq!(updates.len(), 1);
+    check_updates(&old_root, &updates, CommitBudget::default());
+
+    // Nothing to commit.
+    let updates = generator
+        .commit_changes_within(CommitBudget::default())
+        .expect("commit");
+    assert!(updates.is_empty());
+}
+
+#[test]
+fn exceeded_budget() {
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    let result = generator.commit_changes_within(CommitBudget::size(64));
+    assert!(matches!(result, Err(GeneratorError::ExceededBudget(_))));
+    // Nothing is committed.
+    assert_eq!(generator.root(), &old_root);
+    assert!(generator.has_pending_changes());
+    assert_eq!(generator.len(), 100);
+}
@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+use molecule::{bytes::Bytes, prelude::*};
+
+use super::{key, value};
+use crate::{
+    error::{GeneratorError, VerifyError},
+    types::{merge_data_with_proofs, split_data_with_proof, ProofGenerator, H256},
+};
+
+// Only even keys are in the tree, so odd keys are proved as absent.
+fn build_even_generator() -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for index in (0..64).step_by(2) {
+        generator
+            .update(&key(index), value(index, 0))
+            .expect("smt update");
+    }
+    generator
+}
+
+fn keys(indexes: &[u8]) -> Vec<Bytes> {
+    indexes.iter().copied().map(key).collect()
+}
+
+#[test]
+fn merge() {
+    let generator = build_even_generator();
+    let root = generator.root();
+    let groups: [&[u8]; 4] = [&[0, 1, 2], &[40, 63], &[2, 7, 8, 9, 10], &[32]];
+    let items: Vec<_> = groups
+        .iter()
+        .map(|indexes| generator.data_with_proof(keys(indexes)).expect("proof"))
+        .collect();
+    let readers: Vec<_> = items.iter().map(|item| item.as_reader()).collect();
+
+    let merged = merge_data_with_proofs(root, &readers).expect("merge");
+    assert_eq!(merged.as_reader().verify_smt(root), Ok(()));
+    // Duplicated keys are kept once, in the order of first occurrences.
+    let expected_keys = keys(&[0, 1, 2, 40, 63, 7, 8, 9, 10, 32]);
+    let expected = generator
+        .data_with_proof(expected_keys)
+        .expect("data with proof");
+    assert_eq!(merged.as_slice(), expected.as_slice());
+}
+
+#[test]
+fn split() {
+    let generator = build_even_generator();
+    let root = generator.root();
+    let all: Vec<u8> = (0..64).collect();
+    let item = generator.data_with_proof(keys(&all)).expect("proof");
+
+    let subsets: [&[u8]; 5] = [&[0], &[63], &[1, 2, 3], &[62, 10, 33], &all[16..48]];
+    for subset in subsets {
+        let extracted = split_data_with_proof(root, item.as_reader(), keys(subset)).expect("split");
+        assert_eq!(extracted.as_reader().verify_smt(root), Ok(()));
+        let expected = generator.data_with_proof(keys(subset)).expect("proof");
+        assert_eq!(extracted.as_slice(), expected.as_slice());
+    }
+}
+
+#[test]
+fn split_then_merge() {
+    let generator = build_even_generator();
+    let root = generator.root();
+    let indexes = [5, 6, 20, 21, 50];
+    let item = generator.data_with_proof(keys(&indexes)).expect("proof");
+
+    let parts: Vec<_> = indexes
+        .iter()
+        .map(|index| split_data_with_proof(root, item.as_reader(), keys(&[*index])))
+        .collect::<Result<_, _>>()
+        .expect("split");
+    let readers: Vec<_> = parts.iter().map(|part| part.as_reader()).collect();
+    let merged = merge_data_with_proofs(root, &readers).expect("merge");
+    assert_eq!(merged.as_slice(), item.as_slice());
+}
+
+#[test]
+fn invalid_inputs() {
+    let generator = build_even_generator();
+    let root = generator.root();
+    let item = generator.data_with_proof(keys(&[1, 2])).expect("proof");
+    let other = {
+        let mut generator = build_even_generator();
+        generator
+            .update(&key(1), Some(Bytes::new()))
+            .expect("update");
+        generator.data_with_proof(keys(&[3])).expect("proof")
+    };
+
+    let result = merge_data_with_proofs(root, &[item.as_reader(), other.as_reader()]);
+    assert!(matches!(
+        result,
+        Err(GeneratorError::InvalidDataWithProof {
+            index: 1,
+            error: VerifyError::MismatchedRoot
+        })
+    ));
+
+    let result = split_data_with_proof(&H256::zero(), item.as_reader(), keys(&[1]));
+    assert!(matches!(
+        result,
+        Err(GeneratorError::InvalidDataWithProof { index: 0, .. })
+    ));
+
+    let result = split_data_with_proof(root, item.as_reader(), keys(&[1, 3]));
+    assert!(matches!(result, Err(GeneratorError::KeyNotProven(k)) if k == key(3)));
+}
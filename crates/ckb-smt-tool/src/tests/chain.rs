@@ -0,0 +1,99 @@
+use alloc::{vec, vec::Vec};
+
+use molecule::{bytes::Bytes, prelude::*};
+
+use super::{key, value};
+use crate::{
+    error::UpdateError,
+    types::{generated as packed, verifier::hash_to_h256, ProofGenerator, SmtUpdateChain, H256},
+};
+
+fn batches() -> Vec<Vec<(Bytes, Option<Bytes>)>> {
+    vec![
+        vec![(key(1), value(1, 0)), (key(2), value(2, 0))],
+        vec![(key(1), value(1, 1)), (key(3), value(3, 1))],
+        vec![(key(2), None)],
+        vec![(key(3), value(3, 3)), (key(4), value(4, 3))],
+    ]
+}
+
+#[test]
+fn commit_then_verify() {
+    let mut generator = ProofGenerator::new();
+    let mut roots = vec![*generator.root()];
+    let mut expected = ProofGenerator::new();
+    for batch in batches() {
+        for (key, value) in batch {
+            expected.update(&key, value).expect("smt update");
+        }
+        roots.push(*expected.root());
+    }
+
+    let chain = generator.commit_chain(batches()).expect("commit chain");
+    let reader = chain.as_reader();
+    assert_eq!(generator.root(), expected.root());
+    assert_eq!(reader.verify_smt(&roots[0]), Ok(()));
+    assert_eq!(reader.new_root().as_ref(), Some(generator.root()));
+    // All intermediate roots are in the chain.
+    let new_roots: Vec<_> = reader
+        .iter()
+        .map(|smt_update| hash_to_h256(smt_update.new_root().raw_data()))
+        .collect();
+    assert_eq!(new_roots, roots[1..]);
+
+    // Each hop could be applied one by one.
+    let mut replica = ProofGenerator::new();
+    for (smt_update, root) in reader.iter().zip(&roots) {
+        assert_eq!(smt_update.verify_smt(root), Ok(()));
+        replica.apply_update(smt_update).expect("apply update");
+    }
+    assert_eq!(replica.root(), generator.root());
+}
+
+#[test]
+fn pending_changes_in_first_hop() {
+    let mut generator = ProofGenerator::new();
+    generator.append_change(key(9), value(9, 0));
+    let chain = generator.commit_chain(batches()).expect("commit chain");
+    let first = chain.as_reader().get(0).expect("first update");
+    assert_eq!(first.changes().len(), 3);
+    assert_eq!(generator.get(&key(9)).expect("smt get"), value(9, 0));
+}
+
+#[test]
+fn broken_chain() {
+    let mut generator = ProofGenerator::new();
+    let chain = generator.commit_chain(batches()).expect("commit chain");
+    let updates: Vec<_> = chain.clone().into_iter().collect();
+    let build = |updates: Vec<_>| SmtUpdateChain::new_builder().set(updates).build();
+
+    // Mismatched old root.
+    let mut old_root: [u8; 32] = H256::zero().into();
+    old_root[0] = 1;
+    let result = chain.as_reader().verify_smt(&old_root.into());
+    assert_eq!(result, Err(UpdateError::MismatchedOldRoot));
+
+    // A skipped hop.
+    let mut skipped = updates.clone();
+    skipped.remove(1);
+    let result = build(skipped).as_reader().verify_smt(&H256::zero());
+    assert_eq!(result, Err(UpdateError::MismatchedOldRoot));
+
+    // Reordered hops.
+    let mut reordered = updates.clone();
+    reordered.swap(1, 2);
+    let result = build(reordered).as_reader().verify_smt(&H256::zero());
+    assert_eq!(result, Err(UpdateError::MismatchedOldRoot));
+
+    // A forged intermediate root.
+    let mut forged = updates.clone();
+    let mut new_root = forged[1].new_root().as_slice().to_vec();
+    new_root[0] ^= 0x01;
+    let new_root = packed::Hash::from_slice(&new_root).expect("hash");
+    forged[1] = forged[1].clone().as_builder().new_root(new_root).build();
+    let result = build(forged).as_reader().verify_smt(&H256::zero());
+    assert_eq!(result, Err(UpdateError::MismatchedNewRoot));
+
+    let result = build(vec![]).as_reader().verify_smt(&H256::zero());
+    assert_eq!(result, Err(UpdateError::EmptyChain));
+}
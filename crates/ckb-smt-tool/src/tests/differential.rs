@@ -0,0 +1,353 @@
+//! Differential tests between the prover and the verifier.
+//!
+//! Random sequences of changes are committed by the prover, every update and
+//! every proof should be accepted by the verifier, and any mutation of them
+//! should be rejected with a specific error, unless a mutated proof still
+//! proves the same values; proofs split and merged without the tree should be
+//! same as the ones from the tree, and all updates could be squashed into one.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use molecule::{bytes::Bytes, prelude::*};
+use proptest::prelude::*;
+
+use crate::{
+    error::{GeneratorError, UpdateError, VerifyError},
+    types::{
+        generated as packed, merge_data_with_proofs, prover::slice_to_packed_bytes,
+        split_data_with_proof, verifier::hash_to_h256, DataWithProof, KeyValue, ProofGenerator,
+        SmtChange, SmtUpdate, H256,
+    },
+};
+
+type Batch = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
+#[derive(Debug, Clone)]
+enum Op {
+    Commit(Batch),
+    Prove(Vec<Vec<u8>>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Mutation {
+    Root,
+    NewRoot,
+    OldValue,
+    NewValue,
+    Proof,
+}
+
+// A small key space, so keys are updated and removed repeatedly.
+fn key_strategy() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(0u8..4, 1..3)
+}
+
+fn value_strategy() -> impl Strategy<Value = Option<Vec<u8>>> {
+    prop::option::of(prop::collection::vec(any::<u8>(), 0..16))
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        prop::collection::vec((key_strategy(), value_strategy()), 1..8).prop_map(Op::Commit),
+        prop::collection::btree_set(key_strategy(), 1..6)
+            .prop_map(|keys| Op::Prove(keys.into_iter().collect())),
+    ]
+}
+
+fn mutation_strategy() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        Just(Mutation::Root),
+        Just(Mutation::NewRoot),
+        Just(Mutation::OldValue),
+        Just(Mutation::NewValue),
+        Just(Mutation::Proof),
+    ]
+}
+
+fn to_bytes_opt(value: Option<Bytes>) -> packed::BytesOpt {
+    packed::BytesOpt::new_builder()
+        .set(value.map(|v| slice_to_packed_bytes(&v)))
+        .build()
+}
+
+// Returns a different value, which never has the same hash as the original.
+fn mutate_value(value: packed::BytesOpt) -> packed::BytesOpt {
+    let mutated = match value.to_opt() {
+        Some(bytes) => {
+            let mut data = bytes.raw_data().to_vec();
+            if let Some(byte) = data.first_mut() {
+                *byte ^= 0x01;
+            } else {
+                data.push(0);
+            }
+            Some(Bytes::from(data))
+        }
+        None => Some(Bytes::new()),
+    };
+    to_bytes_opt(mutated)
+}
+
+fn mutate_root(root: &H256) -> H256 {
+    let mut data: [u8; 32] = (*root).into();
+    data[0] ^= 0x01;
+    data.into()
+}
+
+fn mutate_proof(proof: packed::Bytes, seed: usize) -> packed::Bytes {
+    let mut data = proof.raw_data().to_vec();
+    let index = seed % data.len();
+    data[index] = data[index].wrapping_add(1 + (seed % 255) as u8);
+    slice_to_packed_bytes(&data)
+}
+
+fn mutate_smt_update(
+    smt_update: &SmtUpdate,
+    old_root: &H256,
+    mutation: Mutation,
+    seed: usize,
+) -> (SmtUpdate, H256) {
+    let changes = smt_update.changes();
+    let index = seed % changes.len();
+    let mutate_change = |f: &dyn Fn(SmtChange) -> SmtChange| {
+        let changes = changes
+            .clone()
+            .into_iter()
+            .enumerate()
+            .map(|(i, change)| if i == index { f(change) } else { change })
+            .collect();
+        packed::SmtChanges::new_builder().set(changes).build()
+    };
+    match mutation {
+        Mutation::Root => (smt_update.clone(), mutate_root(old_root)),
+        Mutation::NewRoot => {
+            let mutated = mutate_root(&hash_to_h256(smt_update.new_root().as_slice()));
+            let mutated = packed::Hash::new_unchecked(Bytes::copy_from_slice(mutated.as_slice()));
+            let smt_update = smt_update.clone().as_builder().new_root(mutated).build();
+            (smt_update, *old_root)
+        }
+        Mutation::OldValue => {
+            let changes = mutate_change(&|change| {
+                let old_value = mutate_value(change.old_value());
+                change.as_builder().old_value(old_value).build()
+            });
+            let smt_update = smt_update.clone().as_builder().changes(changes).build();
+            (smt_update, *old_root)
+        }
+        Mutation::NewValue => {
+            let changes = mutate_change(&|change| {
+                let new_value = mutate_value(change.new_value());
+                change.as_builder().new_value(new_value).build()
+            });
+            let smt_update = smt_update.clone().as_builder().changes(changes).build();
+            (smt_update, *old_root)
+        }
+        Mutation::Proof => {
+            let proof = mutate_proof(smt_update.proof(), seed);
+            let smt_update = smt_update.clone().as_builder().proof(proof).build();
+            (smt_update, *old_root)
+        }
+    }
+}
+
+fn mutate_data_with_proof(
+    data_with_proof: &DataWithProof,
+    root: &H256,
+    mutation: Mutation,
+    seed: usize,
+) -> (DataWithProof, H256) {
+    match mutation {
+        Mutation::Root | Mutation::NewRoot => (data_with_proof.clone(), mutate_root(root)),
+        Mutation::OldValue | Mutation::NewValue => {
+            let data = data_with_proof.data();
+            let index = seed % data.len();
+            let data = data
+                .into_iter()
+                .enumerate()
+                .map(|(i, kv): (usize, KeyValue)| {
+                    if i == index {
+                        let value = mutate_value(kv.value());
+                        kv.as_builder().value(value).build()
+                    } else {
+                        kv
+                    }
+                })
+                .collect();
+            let data = packed::KeyValues::new_builder().set(data).build();
+            let data_with_proof = data_with_proof.clone().as_builder().data(data).build();
+            (data_with_proof, *root)
+        }
+        Mutation::Proof => {
+            let proof = mutate_proof(data_with_proof.proof(), seed);
+            let data_with_proof = data_with_proof.clone().as_builder().proof(proof).build();
+            (data_with_proof, *root)
+        }
+    }
+}
+
+// A mutated proof could still be valid, for example, the height of a merge of
+// zero nodes is changed; then its meaning is checked by the caller.
+fn check_update_mutation(mutation: Mutation, result: &Result<(), UpdateError>) {
+    match mutation {
+        Mutation::Root | Mutation::OldValue => {
+            assert_eq!(result, &Err(UpdateError::MismatchedOldRoot));
+        }
+        Mutation::NewRoot | Mutation::NewValue => {
+            assert_eq!(result, &Err(UpdateError::MismatchedNewRoot));
+        }
+        Mutation::Proof => {
+            assert!(matches!(
+                result,
+                Ok(()) | Err(UpdateError::ComputeOldRoot | UpdateError::MismatchedOldRoot)
+            ));
+        }
+    }
+}
+
+// Same as the update, a mutated proof could still be valid.
+fn check_verify_mutation(mutation: Mutation, result: &Result<(), VerifyError>) {
+    match mutation {
+        Mutation::Root | Mutation::NewRoot | Mutation::OldValue | Mutation::NewValue => {
+            assert_eq!(result, &Err(VerifyError::MismatchedRoot));
+        }
+        Mutation::Proof => {
+            assert!(matches!(
+                result,
+                Ok(()) | Err(VerifyError::ComputeRoot | VerifyError::MismatchedRoot)
+            ));
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn prover_and_verifier_agree(
+        ops in prop::collection::vec(op_strategy(), 1..12),
+        mutations in prop::collection::vec((mutation_strategy(), any::<usize>()), 12),
+    ) {
+        let mut generator = ProofGenerator::new();
+        let mut replica = ProofGenerator::new();
+        let mut model: BTreeMap<Vec<u8>, Vec<u8>> = BTreeMap::new();
+        let mut updates = Vec::new();
+
+        for (op, (mutation, seed)) in ops.into_iter().zip(mutations) {
+            match op {
+                Op::Commit(batch) => {
+                    let old_root = *generator.root();
+                    let mut pending = BTreeMap::new();
+                    for (key, value) in batch {
+                        let value = value.map(Bytes::from);
+                        generator.append_change(Bytes::from(key.clone()), value.clone());
+                        pending.insert(key, value);
+                    }
+                    let smt_update = generator.commit_changes().expect("smt commit");
+                    let new_root = *generator.root();
+
+                    let reader = smt_update.as_reader();
+                    prop_assert_eq!(reader.changes().len(), pending.len());
+                    prop_assert_eq!(hash_to_h256(smt_update.new_root().as_slice()), new_root);
+                    prop_assert!(reader.verify_smt(&old_root).is_ok());
+
+                    let (mutated, root) = mutate_smt_update(&smt_update, &old_root, mutation, seed);
+                    let result = mutated.as_reader().verify_smt(&root);
+                    check_update_mutation(mutation, &result);
+
+                    // An accepted update, even with a mutated proof, changes
+                    // the values in the tree to the claimed new root.
+                    let accepted = if result.is_ok() { mutated.as_reader() } else { reader };
+                    for change in accepted.changes().iter() {
+                        let old_value = change.old_value().to_opt().map(|v| v.raw_data().to_vec());
+                        let stored = replica.get(change.key().raw_data()).expect("smt get");
+                        prop_assert_eq!(old_value.as_deref(), stored.as_deref());
+                    }
+                    replica.apply_update(accepted).expect("apply update");
+                    prop_assert_eq!(replica.root(), &hash_to_h256(accepted.new_root().raw_data()));
+                    prop_assert_eq!(replica.root(), &new_root);
+                    updates.push(smt_update);
+
+                    for (key, value) in pending {
+                        match value {
+                            Some(value) => model.insert(key, value.to_vec()),
+                            None => model.remove(&key),
+                        };
+                    }
+                }
+                Op::Prove(keys) => {
+                    let root = *generator.root();
+                    let keys_bytes = keys.iter().cloned().map(Bytes::from).collect();
+                    let data_with_proof =
+                        generator.data_with_proof(keys_bytes).expect("data with proof");
+
+                    let reader = data_with_proof.as_reader();
+                    prop_assert!(reader.verify_smt(&root).is_ok());
+                    for (key, kv) in keys.iter().zip(reader.data().iter()) {
+                        prop_assert_eq!(kv.key().raw_data(), &key[..]);
+                        let value = kv.value().to_opt().map(|v| v.raw_data().to_vec());
+                        prop_assert_eq!(value.as_ref(), model.get(key));
+                    }
+
+                    // Splits the proof into two parts, then merges them back.
+                    let (head, tail) = keys.split_at(keys.len() / 2);
+                    if !head.is_empty() {
+                        let to_bytes = |keys: &[Vec<u8>]| keys.iter().cloned().map(Bytes::from).collect();
+                        let head = split_data_with_proof(&root, reader, to_bytes(head))
+                            .expect("split");
+                        let tail = split_data_with_proof(&root, reader, to_bytes(tail))
+                            .expect("split");
+                        let merged = merge_data_with_proofs(&root, &[head.as_reader(), tail.as_reader()])
+                            .expect("merge");
+                        prop_assert_eq!(merged.as_slice(), data_with_proof.as_slice());
+                    }
+
+                    let (mutated, root) =
+                        mutate_data_with_proof(&data_with_proof, &root, mutation, seed);
+                    let result = mutated.as_reader().verify_smt(&root);
+                    check_verify_mutation(mutation, &result);
+
+                    // Accepted data, even with a mutated proof, has the values
+                    // in the tree.
+                    if result.is_ok() {
+                        for kv in mutated.as_reader().data().iter() {
+                            let key = kv.key().raw_data();
+                            let value = kv.value().to_opt().map(|v| v.raw_data().to_vec());
+                            prop_assert_eq!(value.as_ref(), model.get(key));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (key, value) in &model {
+            let stored = generator.get(key).expect("smt get");
+            prop_assert_eq!(stored.as_deref(), Some(&value[..]));
+        }
+        // All updates could be squashed into one against the empty tree,
+        // unless the tree is empty again.
+        let readers: Vec<_> = updates.iter().map(|update| update.as_reader()).collect();
+        let result = ProofGenerator::new().squash_updates(&readers);
+        if model.is_empty() {
+            prop_assert!(matches!(result, Err(GeneratorError::NoNetChange)));
+        } else {
+            let squashed = result.expect("squash");
+            prop_assert!(squashed.as_reader().verify_smt(&H256::zero()).is_ok());
+            prop_assert_eq!(hash_to_h256(squashed.new_root().as_slice()), *generator.root());
+        }
+
+        let leaves = generator.iter().map(|(key, value)| (key.to_vec(), value.to_vec()));
+        prop_assert!(leaves.eq(model.clone()));
+        prop_assert_eq!(replica.len(), model.len());
+    }
+}
+
+#[test]
+fn initial_update_with_forged_root() {
+    let mut generator = ProofGenerator::new();
+    generator.append_change(Bytes::from(vec![1u8]), Some(Bytes::from(vec![2u8])));
+    let smt_update = generator.commit_changes().expect("smt commit");
+    let reader = smt_update.as_reader();
+    assert_eq!(reader.verify_smt(&H256::zero()), Ok(()));
+    let forged = mutate_root(&H256::zero());
+    assert_eq!(
+        reader.verify_smt(&forged),
+        Err(UpdateError::MismatchedOldRoot)
+    );
+}
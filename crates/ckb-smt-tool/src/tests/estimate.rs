@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+
+use molecule::prelude::*;
+
+use super::{build_generator, key, value};
+use crate::types::Estimate;
+
+#[test]
+fn estimate_pending_changes() {
+    let mut generator = build_generator(100);
+    assert!(generator.estimate_pending_changes().is_err());
+
+    for i in 90..120 {
+        generator.append_change(key(i), value(i, 1));
+    }
+    let root = *generator.root();
+    let estimate = generator
+        .estimate_pending_changes()
+        .expect("estimate pending changes");
+    // Nothing is committed.
+    assert_eq!(generator.root(), &root);
+    assert!(generator.has_pending_changes());
+
+    let update = generator.commit_changes().expect("commit changes");
+    assert_eq!(estimate.size, update.as_slice().len());
+    assert_eq!(estimate, Estimate::of_smt_update(update.as_reader()));
+}
+
+#[test]
+fn estimate_data_with_proof() {
+    let generator = build_generator(100);
+    let mut last_cycles = 0;
+    for count in [1, 10, 100, 200] {
+        let keys: Vec<_> = (0..count).map(key).collect();
+        let estimate = generator
+            .estimate_data_with_proof(keys.clone())
+            .expect("estimate data with proof");
+        let data_with_proof = generator.data_with_proof(keys).expect("data with proof");
+        assert_eq!(estimate.size, data_with_proof.as_slice().len());
+        assert!(estimate.cycles > last_cycles);
+        last_cycles = estimate.cycles;
+    }
+}
+
+#[test]
+fn update_costs_more_than_proof() {
+    let mut generator = build_generator(100);
+    let keys: Vec<_> = (0..10).map(key).collect();
+    let proof_estimate = generator
+        .estimate_data_with_proof(keys.clone())
+        .expect("estimate data with proof");
+    for key in keys {
+        generator.append_change(key, None);
+    }
+    let update_estimate = generator
+        .estimate_pending_changes()
+        .expect("estimate pending changes");
+    assert!(update_estimate.cycles > proof_estimate.cycles);
+}
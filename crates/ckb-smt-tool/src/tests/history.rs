@@ -0,0 +1,132 @@
+use alloc::{vec, vec::Vec};
+
+use molecule::bytes::Bytes;
+
+use crate::{
+    error::GeneratorError,
+    types::{HistoricalGenerator, PrunePolicy, H256},
+};
+
+fn key() -> Bytes {
+    Bytes::from_static(b"key")
+}
+
+fn value(version: u8) -> Bytes {
+    Bytes::from(vec![version; 8])
+}
+
+// Commits a new value of the same key for each version, and returns the roots
+// from the oldest to the latest, includes the initial empty root.
+fn commit_versions(generator: &mut HistoricalGenerator, versions: u8) -> Vec<H256> {
+    let mut roots = vec![*generator.root()];
+    for version in 0..versions {
+        generator.append_change(key(), Some(value(version)));
+        generator.commit_changes().expect("smt commit");
+        roots.push(*generator.root());
+    }
+    roots
+}
+
+#[test]
+fn proof_at_past_roots() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    let roots = commit_versions(&mut generator, 4);
+    assert_eq!(generator.roots().copied().collect::<Vec<_>>(), roots);
+
+    for (index, root) in roots.iter().enumerate() {
+        let expected = index.checked_sub(1).map(|version| value(version as u8));
+        let stored = generator.get_at(root, &key()).expect("smt get");
+        assert_eq!(stored, expected);
+
+        let data_with_proof = generator
+            .data_with_proof_at(root, vec![key()])
+            .expect("data with proof");
+        let reader = data_with_proof.as_reader();
+        assert_eq!(reader.verify_smt(root), Ok(()));
+        let proved = reader.data().get(0).expect("key value").value().to_opt();
+        assert_eq!(
+            proved.map(|v| Bytes::copy_from_slice(v.raw_data())),
+            expected
+        );
+    }
+}
+
+#[test]
+fn pending_changes_are_not_kept() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    generator.append_change(key(), Some(value(0)));
+    assert_eq!(generator.roots().count(), 1);
+    assert!(generator.generator().has_pending_changes());
+}
+
+#[test]
+fn apply_update() {
+    let mut source = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    source.append_change(key(), Some(value(0)));
+    let smt_update = source.commit_changes().expect("smt commit");
+
+    let mut replica = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    replica
+        .apply_update(smt_update.as_reader())
+        .expect("apply update");
+    assert_eq!(
+        replica.roots().collect::<Vec<_>>(),
+        source.roots().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn keep_latest() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::KeepLatest(2));
+    let roots = commit_versions(&mut generator, 4);
+    assert_eq!(generator.roots().copied().collect::<Vec<_>>(), roots[3..]);
+
+    for root in &roots[..3] {
+        assert!(!generator.contains_root(root));
+        let result = generator.data_with_proof_at(root, vec![key()]);
+        assert!(matches!(result, Err(GeneratorError::UnknownRoot(r)) if r == *root));
+    }
+    let stored = generator.get_at(&roots[3], &key()).expect("smt get");
+    assert_eq!(stored, Some(value(2)));
+}
+
+#[test]
+fn current_root_is_always_kept() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    let roots = commit_versions(&mut generator, 2);
+    generator.set_prune_policy(PrunePolicy::KeepLatest(0));
+    assert_eq!(generator.roots().collect::<Vec<_>>(), vec![&roots[2]]);
+}
+
+#[test]
+fn default_policy_is_bounded() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::default());
+    let roots = commit_versions(&mut generator, 20);
+    assert_eq!(generator.roots().count(), 16);
+    assert!(!generator.contains_root(&roots[4]));
+    let stored = generator.get_at(&roots[5], &key()).expect("smt get");
+    assert_eq!(stored, Some(value(4)));
+}
+
+#[test]
+fn proof_at_past_roots_of_many_keys() {
+    let mut generator = HistoricalGenerator::new(PrunePolicy::KeepAll);
+    let mut roots = vec![*generator.root()];
+    for version in 0..4u8 {
+        for i in 0..8u8 {
+            // Removes the keys of the last version at the end.
+            let new_value = (version < 3 || i % 2 == 0).then(|| value(version + i));
+            generator.append_change(Bytes::from(vec![i]), new_value);
+        }
+        generator.commit_changes().expect("smt commit");
+        roots.push(*generator.root());
+    }
+
+    let keys: Vec<_> = (0..8u8).map(|i| Bytes::from(vec![i])).collect();
+    for root in &roots {
+        let data_with_proof = generator
+            .data_with_proof_at(root, keys.clone())
+            .expect("data with proof");
+        assert_eq!(data_with_proof.as_reader().verify_smt(root), Ok(()));
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::{format, vec};
+
+use molecule::prelude::*;
+
+use crate::types::{
+    json::{JsonDataWithProof, JsonSmtUpdate},
+    Bytes, DataWithProof, ProofGenerator, SmtUpdate,
+};
+
+fn build_generator() -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = [i; 4];
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.update(&k, v).expect("smt update");
+    }
+    generator
+}
+
+#[test]
+fn smt_update_roundtrip() {
+    let mut generator = build_generator();
+    generator.append_change(Bytes::copy_from_slice(&[10u8; 4]), None);
+    generator.append_change(Bytes::new(), Some(Bytes::new()));
+    generator.append_change(
+        Bytes::copy_from_slice(&[25u8; 4]),
+        Some(Bytes::copy_from_slice(&[25u8; 20])),
+    );
+    let smt_update = generator.commit_changes().expect("smt commit");
+
+    let json = JsonSmtUpdate::from(smt_update.clone());
+    let json_str = serde_json::to_string(&json).expect("serialize");
+    let json_decoded: JsonSmtUpdate = serde_json::from_str(&json_str).expect("deserialize");
+    assert_eq!(json, json_decoded);
+
+    let smt_update_decoded = SmtUpdate::from(json_decoded);
+    assert_eq!(smt_update.as_slice(), smt_update_decoded.as_slice());
+}
+
+#[test]
+fn data_with_proof_roundtrip() {
+    let generator = build_generator();
+    let keys = vec![
+        Bytes::copy_from_slice(&[5u8; 4]),
+        Bytes::copy_from_slice(&[30u8; 4]),
+    ];
+    let data_with_proof = generator.data_with_proof(keys).expect("data with proof");
+
+    let json = JsonDataWithProof::from(data_with_proof.clone());
+    let json_str = serde_json::to_string(&json).expect("serialize");
+    let json_decoded: JsonDataWithProof = serde_json::from_str(&json_str).expect("deserialize");
+    assert_eq!(json, json_decoded);
+
+    let data_with_proof_decoded = DataWithProof::from(json_decoded);
+    assert_eq!(
+        data_with_proof.as_slice(),
+        data_with_proof_decoded.as_slice()
+    );
+}
+
+#[test]
+fn hex_format() {
+    let json_str =
+        r#"{"data":[{"key":"0x0102","value":null},{"key":"0x","value":"0xff"}],"proof":"0x4c"}"#;
+    let json: JsonDataWithProof = serde_json::from_str(json_str).expect("deserialize");
+    assert_eq!(json.data[0].key.0.as_ref(), &[1, 2]);
+    assert!(json.data[0].value.is_none());
+    assert!(json.data[1].key.0.is_empty());
+    assert_eq!(json.data[1].value.as_ref().unwrap().0.as_ref(), &[0xff]);
+    assert_eq!(serde_json::to_string(&json).expect("serialize"), json_str);
+
+    for invalid in ["0102", "0x010", "0xzz"] {
+        let json_str = format!(r#"{{"data":[],"proof":"{invalid}"}}"#);
+        assert!(serde_json::from_str::<JsonDataWithProof>(&json_str).is_err());
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(feature = "with-prover")]
+mod budget;
+#[cfg(feature = "with-prover")]
+mod bundle;
+#[cfg(feature = "with-prover")]
+mod chain;
+#[cfg(feature = "with-prover")]
+mod differential;
+#[cfg(feature = "with-prover")]
+mod estimate;
+#[cfg(feature = "with-prover")]
+mod history;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "with-prover")]
+mod prover;
+#[cfg(feature = "with-prover")]
+mod read_write;
+#[cfg(feature = "with-prover")]
+mod rebase;
+#[cfg(feature = "with-prover")]
+mod snapshot;
+#[cfg(feature = "with-prover")]
+mod squash;
+#[cfg(feature = "with-ckb-types")]
+mod tx_builder;
+mod unique_id;
+#[cfg(feature = "with-ckb-types")]
+mod witness;
+
+#[cfg(feature = "with-prover")]
+use alloc::vec;
+
+#[cfg(feature = "with-prover")]
+use molecule::bytes::Bytes;
+
+#[cfg(feature = "with-prover")]
+use crate::types::ProofGenerator;
+
+#[cfg(feature = "with-prover")]
+fn key(index: u8) -> Bytes {
+    Bytes::from(vec![index; 4])
+}
+
+#[cfg(feature = "with-prover")]
+fn value(index: u8, version: u8) -> Option<Bytes> {
+    Some(Bytes::from(vec![index, version]))
+}
+
+// Builds a tree with the first `count` keys, all values are at version 0.
+#[cfg(feature = "with-prover")]
+fn build_generator(count: u8) -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for index in 0..count {
+        generator
+            .update(&key(index), value(index, 0))
+            .expect("smt update");
+    }
+    generator
+}
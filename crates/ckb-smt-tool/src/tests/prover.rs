@@ -0,0 +1,189 @@
+use alloc::{vec, vec::Vec};
+use std::{env, println, time::Instant};
+
+use molecule::{bytes::Bytes, prelude::*};
+
+use crate::types::ProofGenerator;
+
+fn key(index: u8) -> Bytes {
+    Bytes::from(vec![index; 2])
+}
+
+fn value(index: u8) -> Bytes {
+    Bytes::from(vec![index; 8])
+}
+
+fn leaves(generator: &ProofGenerator) -> Vec<(Bytes, Bytes)> {
+    generator
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
+#[test]
+fn enumerate_after_update() {
+    let mut generator = ProofGenerator::new();
+    assert!(generator.is_empty());
+    for index in [3, 1, 2] {
+        generator
+            .update(&key(index), Some(value(index)))
+            .expect("smt update");
+    }
+    generator
+        .update(&key(1), Some(value(9)))
+        .expect("smt update");
+    generator.update(&key(2), None).expect("smt update");
+    // Removing an absent key changes nothing.
+    generator.update(&key(4), None).expect("smt update");
+
+    assert_eq!(generator.len(), 2);
+    assert_eq!(
+        generator.keys().cloned().collect::<Vec<_>>(),
+        vec![key(1), key(3)]
+    );
+    assert_eq!(
+        leaves(&generator),
+        vec![(key(1), value(9)), (key(3), value(3))]
+    );
+}
+
+#[test]
+fn enumerate_after_commit_and_apply() {
+    let mut generator = ProofGenerator::new();
+    let mut replica = ProofGenerator::new();
+    for index in 0..4 {
+        generator.append_change(key(index), Some(value(index)));
+    }
+    let smt_update = generator.commit_changes().expect("smt commit");
+    replica
+        .apply_update(smt_update.as_reader())
+        .expect("apply update");
+    assert_eq!(generator.len(), 4);
+    assert_eq!(leaves(&replica), leaves(&generator));
+
+    generator.append_change(key(0), None);
+    generator.append_change(key(5), Some(value(5)));
+    // Pending changes are not enumerated.
+    assert_eq!(generator.len(), 4);
+    let smt_update = generator.commit_changes().expect("smt commit");
+    replica
+        .apply_update(smt_update.as_reader())
+        .expect("apply update");
+    let expected: Vec<_> = [1, 2, 3, 5]
+        .into_iter()
+        .map(|index| (key(index), value(index)))
+        .collect();
+    assert_eq!(leaves(&generator), expected);
+    assert_eq!(leaves(&replica), expected);
+}
+
+#[test]
+fn prove_all_keys() {
+    let mut generator = ProofGenerator::new();
+    for index in 0..8 {
+        generator.append_change(key(index), Some(value(index)));
+    }
+    generator.commit_changes().expect("smt commit");
+    let keys = generator.keys().cloned().collect();
+    let data_with_proof = generator.data_with_proof(keys).expect("data with proof");
+    let reader = data_with_proof.as_reader();
+    assert_eq!(reader.verify_smt(generator.root()), Ok(()));
+    assert_eq!(reader.data().len(), 8);
+}
+
+#[test]
+fn update_all() {
+    let mut expected = ProofGenerator::new();
+    let mut actual = ProofGenerator::new();
+    for index in 0..4 {
+        expected
+            .update(&key(index), Some(value(index)))
+            .expect("smt update");
+        actual
+            .update(&key(index), Some(value(index)))
+            .expect("smt update");
+    }
+
+    // Removes, updates and inserts; the last value of a key is used.
+    let changes = vec![
+        (key(0), None),
+        (key(1), Some(value(8))),
+        (key(5), Some(value(5))),
+        (key(1), Some(value(9))),
+        (key(6), Some(value(6))),
+        (key(6), None),
+    ];
+    for (key, value) in changes.clone() {
+        expected.update(&key, value).expect("smt update");
+    }
+    let root = *actual.update_all(changes).expect("smt update all");
+    assert_eq!(&root, expected.root());
+    assert_eq!(leaves(&actual), leaves(&expected));
+
+    // Removes a leaf which is not changed before.
+    expected.update(&key(2), None).expect("smt update");
+    let root = *actual
+        .update_all(vec![(key(2), None)])
+        .expect("smt update all");
+    assert_eq!(&root, expected.root());
+    assert_eq!(leaves(&actual), leaves(&expected));
+
+    // The branches are same, so are the proofs, includes the absent keys.
+    let keys: Vec<_> = (0..8).map(key).collect();
+    let proof = |generator: &ProofGenerator| {
+        let data_with_proof = generator
+            .data_with_proof(keys.clone())
+            .expect("data with proof");
+        data_with_proof.as_slice().to_vec()
+    };
+    assert_eq!(proof(&actual), proof(&expected));
+}
+
+// Compares the bulk loading with the one-by-one updating; run it by:
+//
+// ```sh
+// cargo test -p ckb-smt-tool --release benchmark -- --ignored --nocapture
+// ```
+//
+// Set `BENCHMARK_ENTRIES` to change the count of entries; each entry takes
+// about 50 KiB memory in the default store, since a branch is stored for each
+// height. Writing these branches takes most of the time in both ways, so the
+// speedup is only reported.
+#[test]
+#[ignore]
+fn benchmark_bulk_load() {
+    let count: u32 = env::var("BENCHMARK_ENTRIES")
+        .map(|s| s.parse().expect("parse BENCHMARK_ENTRIES"))
+        .unwrap_or(50_000);
+
+    let entries = || {
+        (0..count).map(|index| {
+            let key = Bytes::copy_from_slice(&index.to_le_bytes());
+            let value = Bytes::copy_from_slice(&index.to_be_bytes());
+            (key, Some(value))
+        })
+    };
+
+    let now = Instant::now();
+    let mut one_by_one = ProofGenerator::new();
+    for (key, value) in entries() {
+        one_by_one.update(&key, value).expect("smt update");
+    }
+    let one_by_one_elapsed = now.elapsed();
+    let expected = *one_by_one.root();
+    drop(one_by_one);
+
+    let now = Instant::now();
+    let mut bulk = ProofGenerator::new();
+    bulk.update_all(entries()).expect("smt update all");
+    let bulk_elapsed = now.elapsed();
+
+    assert_eq!(bulk.root(), &expected);
+    println!("load {count} entries:");
+    println!("- update:     {one_by_one_elapsed:?}");
+    println!("- update_all: {bulk_elapsed:?}");
+    println!(
+        "- speedup:    {:.2}x",
+        one_by_one_elapsed.as_secs_f64() / bulk_elapsed.as_secs_f64()
+    );
+}
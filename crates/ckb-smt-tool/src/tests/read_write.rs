@@ -0,0 +1,171 @@
+use alloc::{vec, vec::Vec};
+
+use ckb_hash::blake2b_256;
+use molecule::{bytes::Bytes, prelude::*};
+
+use super::{build_generator, key, value};
+use crate::{
+    error::UpdateError,
+    types::{
+        generated as packed, prover::slice_to_packed_bytes, verifier::hash_to_h256, Estimate,
+        ProofGenerator, SmtReadWrite, H256,
+    },
+};
+
+fn prepare() -> ProofGenerator {
+    let mut generator = build_generator(50);
+    generator.append_change(key(1), value(1, 1));
+    generator.append_change(key(2), value(2, 1));
+    generator.append_change(key(3), None);
+    generator.append_change(key(100), value(100, 1));
+    generator
+}
+
+#[test]
+fn commit_then_verify() {
+    let mut expected = prepare();
+    expected.commit_changes().expect("commit changes");
+
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    // A duplicated key, an absent key, and a changed key.
+    let read_keys = vec![key(10), key(11), key(10), key(200), key(1)];
+    let read_write = generator
+        .commit_read_write(read_keys)
+        .expect("commit read write");
+    let reader = read_write.as_reader();
+    assert_eq!(reader.verify_smt(&old_root), Ok(()));
+    assert_eq!(generator.root(), expected.root());
+    assert_eq!(
+        &hash_to_h256(reader.new_root().raw_data()),
+        generator.root()
+    );
+    assert!(!generator.has_pending_changes());
+
+    let reads: Vec<_> = reader
+        .reads()
+        .iter()
+        .map(|kv| {
+            let value = kv.value().to_opt().map(|v| v.raw_data().to_vec());
+            (kv.key().raw_data().to_vec(), value)
+        })
+        .collect();
+    // Sorted by the hashes of the keys.
+    let mut expected_reads = vec![
+        (key(10).to_vec(), Some(vec![10, 0])),
+        (key(11).to_vec(), Some(vec![11, 0])),
+        (key(200).to_vec(), None),
+    ];
+    expected_reads.sort_by_key(|(key, _)| H256::from(blake2b_256(key)));
+    assert_eq!(reads, expected_reads);
+    assert_eq!(reader.changes().len(), 4);
+}
+
+#[test]
+fn smaller_than_separate_witnesses() {
+    let read_keys: Vec<_> = (10..20).map(key).collect();
+
+    let mut separate = prepare();
+    let data_with_proof = separate
+        .data_with_proof(read_keys.clone())
+        .expect("data with proof");
+    let update = separate.commit_changes().expect("commit changes");
+
+    let mut generator = prepare();
+    let read_write = generator
+        .commit_read_write(read_keys)
+        .expect("commit read write");
+    let estimate = Estimate::of_smt_read_write(read_write.as_reader());
+    assert_eq!(estimate.size, read_write.as_slice().len());
+    assert!(estimate.size < update.as_slice().len() + data_with_proof.as_slice().len());
+}
+
+#[test]
+fn reads_only() {
+    let mut generator = ProofGenerator::new();
+    for i in 0..10 {
+        generator.update(&key(i), value(i, 0)).expect("smt update");
+    }
+    let root = *generator.root();
+    let read_write = generator
+        .commit_read_write(vec![key(1), key(2)])
+        .expect("commit read write");
+    let reader = read_write.as_reader();
+    assert_eq!(reader.verify_smt(&root), Ok(()));
+    assert_eq!(hash_to_h256(reader.new_root().raw_data()), root);
+    assert!(reader.changes().is_empty());
+}
+
+#[test]
+fn reject_forged() {
+    let mut generator = prepare();
+    let old_root = *generator.root();
+    let read_write = generator
+        .commit_read_write(vec![key(10)])
+        .expect("commit read write");
+    let reader = read_write.as_reader();
+
+    assert_eq!(
+        reader.verify_smt(&H256::zero()),
+        Err(UpdateError::MismatchedOldRoot)
+    );
+
+    // Forge the value of the read key.
+    let forged_kv = packed::KeyValue::new_builder()
+        .key(reader.reads().get(0).unwrap().key().to_entity())
+        .value(
+            packed::BytesOpt::new_builder()
+                .set(Some(slice_to_packed_bytes(&[10, 9])))
+                .build(),
+        )
+        .build();
+    let forged = read_write
+        .clone()
+        .as_builder()
+        .reads(packed::KeyValues::new_builder().push(forged_kv).build())
+        .build();
+    assert_eq!(
+        forged.as_reader().verify_smt(&old_root),
+        Err(UpdateError::MismatchedOldRoot)
+    );
+
+    // Forge the new root.
+    let forged = read_write
+        .clone()
+        .as_builder()
+        .new_root(packed::Hash::new_unchecked(Bytes::from(vec![1u8; 32])))
+        .build();
+    assert_eq!(
+        forged.as_reader().verify_smt(&old_root),
+        Err(UpdateError::MismatchedNewRoot)
+    );
+
+    // Read a key which is also changed.
+    let changed = reader.changes().get(0).unwrap();
+    let duplicated_kv = packed::KeyValue::new_builder()
+        .key(changed.key().to_entity())
+        .value(changed.old_value().to_entity())
+        .build();
+    let forged = read_write
+        .clone()
+        .as_builder()
+        .reads(
+            reader
+                .reads()
+                .to_entity()
+                .as_builder()
+                .push(duplicated_kv)
+                .build(),
+        )
+        .build();
+    assert_eq!(
+        forged.as_reader().verify_smt(&old_root),
+        Err(UpdateError::DuplicatedKey)
+    );
+}
+
+#[test]
+fn default_is_valid() {
+    let default = SmtReadWrite::default();
+    assert!(SmtReadWrite::from_slice(default.as_slice()).is_ok());
+}
@@ -0,0 +1,135 @@
+use alloc::{vec, vec::Vec};
+
+use super::{build_generator, key, value};
+use crate::{
+    error::{GeneratorError, UpdateError},
+    types::{Conflict, ProofGenerator, H256},
+};
+
+#[test]
+fn rebase_on_foreign_update() {
+    let mut writer_a = build_generator(10);
+    let mut writer_b = build_generator(10);
+    let old_root = *writer_a.root();
+
+    writer_a.append_change(key(1), value(1, 1));
+    writer_a.append_change(key(2), value(2, 1));
+    writer_a.append_change(key(4), None);
+    writer_b.append_change(key(2), value(2, 2));
+    writer_b.append_change(key(3), value(3, 2));
+    writer_b.append_change(key(4), None);
+    writer_b.append_change(key(20), value(20, 2));
+
+    // Both prepare their updates against the same root, A lands first.
+    let update_b = writer_b.prepare_changes().expect("prepare changes");
+    assert_eq!(update_b.as_reader().verify_smt(&old_root), Ok(()));
+    assert_eq!(writer_b.root(), &old_root);
+    let update_a = writer_a.compare_and_commit().expect("compare and commit");
+
+    let rebase = writer_b
+        .rebase(update_a.as_reader())
+        .expect("rebase on update a");
+    assert_eq!(writer_b.root(), writer_a.root());
+    // The removal of the key 4 is already applied, so it's not a conflict.
+    assert_eq!(
+        rebase.conflicts,
+        vec![Conflict {
+            key: key(2),
+            expected: value(2, 0),
+            actual: value(2, 1),
+            new_value: value(2, 2),
+        }]
+    );
+    let update = rebase.update.expect("rebased update");
+    assert_eq!(update.as_reader().verify_smt(writer_a.root()), Ok(()));
+    let keys: Vec<_> = update
+        .as_reader()
+        .changes()
+        .iter()
+        .map(|change| change.key().raw_data().to_vec())
+        .collect();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&key(3).to_vec()));
+    assert!(keys.contains(&key(20).to_vec()));
+
+    // The rebased update lands.
+    writer_b.compare_and_commit().expect("compare and commit");
+    writer_a
+        .apply_update(update.as_reader())
+        .expect("apply update");
+    assert_eq!(writer_a.root(), writer_b.root());
+    assert_eq!(writer_b.get(&key(2)).expect("get"), value(2, 1));
+    assert_eq!(writer_b.get(&key(3)).expect("get"), value(3, 2));
+}
+
+#[test]
+fn compare_and_swap() {
+    let mut generator = build_generator(10);
+    let root = *generator.root();
+    generator.append_change_if(key(1), value(1, 0), value(1, 1));
+    generator.append_change_if(key(2), value(2, 9), value(2, 1));
+    generator.append_change_if(key(30), None, value(30, 1));
+
+    let conflicts = generator.conflicts().expect("conflicts");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].key, key(2));
+    assert_eq!(conflicts[0].actual, value(2, 0));
+
+    let result = generator.prepare_changes();
+    assert!(matches!(result, Err(GeneratorError::Conflicts(keys)) if keys == vec![key(2)]));
+    let result = generator.compare_and_commit();
+    assert!(matches!(result, Err(GeneratorError::Conflicts(keys)) if keys == vec![key(2)]));
+    assert_eq!(generator.root(), &root);
+    assert!(generator.has_pending_changes());
+
+    // Fix the expected value.
+    generator.append_change_if(key(2), value(2, 0), value(2, 1));
+    let update = generator.compare_and_commit().expect("compare and commit");
+    assert_eq!(update.as_reader().verify_smt(&root), Ok(()));
+}
+
+#[test]
+fn expected_value_of_repeated_changes() {
+    let mut generator = build_generator(10);
+    generator.append_change(key(1), value(1, 1));
+    // The expected value is still the value in the tree.
+    generator.append_change(key(1), value(1, 2));
+    assert!(generator.conflicts().expect("conflicts").is_empty());
+    generator.compare_and_commit().expect("compare and commit");
+    assert_eq!(generator.get(&key(1)).expect("get"), value(1, 2));
+}
+
+#[test]
+fn rebase_on_own_update() {
+    let mut generator = build_generator(10);
+    generator.append_change(key(1), value(1, 1));
+    generator.append_change(key(2), None);
+    let update = generator.prepare_changes().expect("prepare changes");
+
+    let rebase = generator.rebase(update.as_reader()).expect("rebase");
+    assert!(rebase.conflicts.is_empty());
+    assert!(rebase.update.is_none());
+    assert!(!generator.has_pending_changes());
+    assert_eq!(generator.get(&key(1)).expect("get"), value(1, 1));
+}
+
+#[test]
+fn rebase_on_invalid_update() {
+    let mut other = ProofGenerator::new();
+    other.append_change(key(1), value(1, 1));
+    let update = other.commit_changes().expect("commit changes");
+
+    let mut generator = build_generator(10);
+    let root = *generator.root();
+    generator.append_change(key(3), value(3, 1));
+    let result = generator.rebase(update.as_reader());
+    assert!(matches!(
+        result,
+        Err(GeneratorError::InvalidForeignUpdate(
+            UpdateError::MismatchedOldRoot
+        ))
+    ));
+    assert_eq!(generator.root(), &root);
+    assert_ne!(root, H256::zero());
+    assert!(generator.has_pending_changes());
+}
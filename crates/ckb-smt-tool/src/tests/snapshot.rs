@@ -0,0 +1,127 @@
+use alloc::{format, vec, vec::Vec};
+use std::{env, fs, process};
+
+use molecule::{bytes::Bytes, prelude::*};
+
+use crate::{error::GeneratorError, types::ProofGenerator};
+
+fn key(index: u8) -> Bytes {
+    Bytes::from(vec![index; 4])
+}
+
+fn value(index: u8) -> Bytes {
+    Bytes::from(vec![index; usize::from(index)])
+}
+
+fn build_generator(indexes: impl Iterator<Item = u8>) -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for index in indexes {
+        generator.append_change(key(index), Some(value(index)));
+    }
+    generator.commit_changes().expect("smt commit");
+    generator
+}
+
+fn to_bytes(generator: &ProofGenerator) -> Vec<u8> {
+    let mut buf = Vec::new();
+    generator.write_snapshot(&mut buf).expect("write snapshot");
+    buf
+}
+
+#[test]
+fn save_then_load() {
+    let mut generator = build_generator(0..16);
+    // Removed leaves should not be saved.
+    generator.append_change(key(3), None);
+    generator.commit_changes().expect("smt commit");
+
+    let path = env::temp_dir().join(format!("ckb-smt-tool-snapshot-{}", process::id()));
+    generator.save_snapshot(&path).expect("save snapshot");
+    let loaded = ProofGenerator::load_snapshot(&path);
+    fs::remove_file(&path).expect("remove snapshot");
+    let loaded = loaded.expect("load snapshot");
+
+    assert_eq!(loaded.root(), generator.root());
+    assert!(loaded.iter().eq(generator.iter()));
+    for index in 0..16 {
+        let expected = (index != 3).then(|| value(index));
+        assert_eq!(loaded.get(&key(index)).expect("smt get"), expected);
+    }
+    let keys: Vec<_> = (0..16).map(key).collect();
+    let expected = generator
+        .data_with_proof(keys.clone())
+        .expect("data with proof");
+    let actual = loaded.data_with_proof(keys).expect("data with proof");
+    assert_eq!(actual.as_slice(), expected.as_slice());
+}
+
+#[test]
+fn empty_tree() {
+    let generator = ProofGenerator::new();
+    let loaded = ProofGenerator::read_snapshot(&mut &to_bytes(&generator)[..]).expect("read");
+    assert!(loaded.root().is_zero());
+}
+
+#[test]
+fn deterministic() {
+    let generator = build_generator(0..16);
+    let reversed = build_generator((0..16).rev());
+    assert_eq!(to_bytes(&generator), to_bytes(&reversed));
+}
+
+#[test]
+fn pending_changes_are_not_saved() {
+    let mut generator = build_generator(0..4);
+    generator.append_change(key(4), Some(value(4)));
+    let loaded = ProofGenerator::read_snapshot(&mut &to_bytes(&generator)[..]).expect("read");
+    assert!(!loaded.has_pending_changes());
+    assert_eq!(loaded.get(&key(4)).expect("smt get"), None);
+}
+
+#[test]
+fn mismatched_root() {
+    let generator = build_generator(1..4);
+    let bytes = to_bytes(&generator);
+    // Corrupt the root, then the last byte of the last value.
+    for index in [5, bytes.len() - 1] {
+        let mut corrupted = bytes.clone();
+        corrupted[index] ^= 0x01;
+        let result = ProofGenerator::read_snapshot(&mut &corrupted[..]);
+        assert!(matches!(
+            result,
+            Err(GeneratorError::MismatchedSnapshotRoot { .. })
+        ));
+    }
+}
+
+#[test]
+fn invalid_snapshot() {
+    let generator = build_generator(1..4);
+    let bytes = to_bytes(&generator);
+
+    let mut unknown_magic = bytes.clone();
+    unknown_magic[0] ^= 0x01;
+    let mut unknown_version = bytes.clone();
+    unknown_version[4] = 0xff;
+    let truncated = bytes[..bytes.len() - 1].to_vec();
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    // Swap the keys of the first two leaves.
+    let mut unsorted = bytes.clone();
+    let first = 4 + 1 + 32 + 8 + 4;
+    let second = first + 4 + 4 + 1 + 4;
+    let first_key = unsorted[first..first + 4].to_vec();
+    unsorted.copy_within(second..second + 4, first);
+    unsorted[second..second + 4].copy_from_slice(&first_key);
+
+    for corrupted in [
+        unknown_magic,
+        unknown_version,
+        truncated,
+        trailing,
+        unsorted,
+    ] {
+        let result = ProofGenerator::read_snapshot(&mut &corrupted[..]);
+        assert!(matches!(result, Err(GeneratorError::InvalidSnapshot(_))));
+    }
+}
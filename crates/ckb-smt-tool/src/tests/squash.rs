@@ -0,0 +1,107 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use molecule::bytes::Bytes;
+
+use super::{build_generator, key, value};
+use crate::{
+    error::{GeneratorError, UpdateError},
+    types::{generated as packed, ProofGenerator, SmtUpdate},
+};
+
+fn commit(generator: &mut ProofGenerator, batch: &[(u8, Option<Bytes>)]) -> SmtUpdate {
+    for (index, value) in batch {
+        generator.append_change(key(*index), value.clone());
+    }
+    generator.commit_changes().expect("smt commit")
+}
+
+type Changes = BTreeMap<Bytes, (Option<Bytes>, Option<Bytes>)>;
+
+fn to_changes(smt_update: &SmtUpdate) -> Changes {
+    let to_bytes = |v: Option<packed::Bytes>| v.map(|v| v.raw_data());
+    smt_update
+        .changes()
+        .into_iter()
+        .map(|change| {
+            let old_value = to_bytes(change.old_value().to_opt());
+            let new_value = to_bytes(change.new_value().to_opt());
+            (change.key().raw_data(), (old_value, new_value))
+        })
+        .collect()
+}
+
+#[test]
+fn squash() {
+    let base = build_generator(16);
+    let mut generator = build_generator(16);
+    let updates = [
+        commit(
+            &mut generator,
+            &[(1, value(1, 1)), (2, None), (20, value(20, 1))],
+        ),
+        // Updated again.
+        commit(&mut generator, &[(1, value(1, 2)), (3, value(3, 2))]),
+        // Reverted, inserted then removed, and removed then inserted.
+        commit(
+            &mut generator,
+            &[(3, value(3, 0)), (20, None), (2, value(2, 3))],
+        ),
+        commit(&mut generator, &[(4, None), (21, value(21, 4))]),
+    ];
+    let readers: Vec<_> = updates.iter().map(|update| update.as_reader()).collect();
+    let squashed = base.squash_updates(&readers).expect("squash");
+
+    let reader = squashed.as_reader();
+    assert_eq!(reader.verify_smt(base.root()), Ok(()));
+    assert_eq!(reader.new_root().raw_data(), generator.root().as_slice());
+    let expected: Changes = [
+        (key(1), (value(1, 0), value(1, 2))),
+        (key(2), (value(2, 0), value(2, 3))),
+        (key(4), (value(4, 0), None)),
+        (key(21), (None, value(21, 4))),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(to_changes(&squashed), expected);
+
+    let mut replica = build_generator(16);
+    replica.apply_update(reader).expect("apply update");
+    assert_eq!(replica.root(), generator.root());
+}
+
+#[test]
+fn invalid_updates() {
+    let base = build_generator(16);
+    let mut generator = build_generator(16);
+    let first = commit(&mut generator, &[(1, value(1, 1))]);
+    let second = commit(&mut generator, &[(2, value(2, 2))]);
+    let third = commit(&mut generator, &[(3, value(3, 3))]);
+
+    let result = ProofGenerator::new().squash_updates(&[first.as_reader()]);
+    assert!(matches!(
+        result,
+        Err(GeneratorError::InvalidSmtUpdate {
+            index: 0,
+            error: UpdateError::MismatchedOldRoot
+        })
+    ));
+    let result = base.squash_updates(&[first.as_reader(), third.as_reader()]);
+    assert!(matches!(
+        result,
+        Err(GeneratorError::InvalidSmtUpdate { index: 1, .. })
+    ));
+    let result = base.squash_updates(&[first.as_reader(), second.as_reader()]);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn no_net_changes() {
+    let base = build_generator(16);
+    let mut generator = build_generator(16);
+    let first = commit(&mut generator, &[(1, value(1, 1)), (30, value(30, 1))]);
+    let second = commit(&mut generator, &[(1, value(1, 0)), (30, None)]);
+    let result = base.squash_updates(&[first.as_reader(), second.as_reader()]);
+    assert!(matches!(result, Err(GeneratorError::NoNetChange)));
+    let result = base.squash_updates(&[]);
+    assert!(matches!(result, Err(GeneratorError::NoNetChange)));
+}
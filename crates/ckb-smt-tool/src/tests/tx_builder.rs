@@ -0,0 +1,157 @@
+use alloc::vec;
+use ckb_types::{bytes::Bytes, packed, prelude::*};
+
+use crate::{
+    error::TxBuilderError,
+    tx_builder::{build_create_tx, build_destroy_tx, build_update_tx},
+    types::{ProofGenerator, H256},
+    unique_id::calculate_unique_id,
+    witness::{extract_smt_update, WitnessField},
+};
+
+fn mock_out_point(seed: u8) -> packed::OutPoint {
+    packed::OutPoint::new_builder()
+        .tx_hash([seed; 32].pack())
+        .index(0u32.pack())
+        .build()
+}
+
+fn mock_cell() -> packed::CellOutput {
+    let lock = packed::Script::new_builder()
+        .code_hash([1u8; 32].pack())
+        .build();
+    let type_script = packed::Script::new_builder()
+        .code_hash([2u8; 32].pack())
+        .args(Bytes::from(vec![3u8; 32]).pack())
+        .build();
+    packed::CellOutput::new_builder()
+        .capacity(1000u64.pack())
+        .lock(lock)
+        .type_(Some(type_script).pack())
+        .build()
+}
+
+fn build_generator() -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = [i; 4];
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.update(&k, v).expect("smt update");
+    }
+    generator
+}
+
+#[test]
+fn create_empty() {
+    let mut generator = ProofGenerator::new();
+    let input = packed::CellInput::new_builder()
+        .previous_output(mock_out_point(1))
+        .build();
+    let type_script = mock_cell().type_().to_opt().expect("type script");
+    let tx = build_create_tx(&mut generator, input.clone(), type_script, mock_cell())
+        .expect("build create tx");
+
+    let output = tx.output(0).expect("output");
+    let args = output.type_().to_opt().expect("type script").args();
+    let unique_id = calculate_unique_id(input.as_slice(), 0);
+    assert_eq!(args.raw_data().as_ref(), &unique_id);
+    let output_data = tx.outputs_data().get(0).expect("output data");
+    assert_eq!(output_data.raw_data().as_ref(), &[0u8; 32]);
+    assert!(tx.witnesses().is_empty());
+}
+
+#[test]
+fn create_with_initial_data() {
+    let mut generator = ProofGenerator::new();
+    generator.append_change(Bytes::from(vec![1u8]), Some(Bytes::from(vec![2u8])));
+    let input = packed::CellInput::new_builder()
+        .previous_output(mock_out_point(1))
+        .build();
+    let type_script = mock_cell().type_().to_opt().expect("type script");
+    let tx =
+        build_create_tx(&mut generator, input, type_script, mock_cell()).expect("build create tx");
+
+    let output_data = tx.outputs_data().get(0).expect("output data");
+    assert_eq!(output_data.raw_data().as_ref(), generator.root().as_slice());
+    let smt_update = extract_smt_update(&tx, 0, WitnessField::OutputType).expect("smt update");
+    assert!(smt_update.as_reader().verify_smt(&H256::zero()).is_ok());
+}
+
+#[test]
+fn create_with_non_empty_tree() {
+    let mut generator = build_generator();
+    generator.append_change(Bytes::from(vec![1u8]), Some(Bytes::from(vec![2u8])));
+    let input = packed::CellInput::new_builder()
+        .previous_output(mock_out_point(1))
+        .build();
+    let type_script = mock_cell().type_().to_opt().expect("type script");
+    let result = build_create_tx(&mut generator, input, type_script, mock_cell());
+    assert!(matches!(
+        result,
+        Err(TxBuilderError::NonEmptyInitialTree(_))
+    ));
+}
+
+#[test]
+fn create_with_committed_tree() {
+    let mut generator = build_generator();
+    let input = packed::CellInput::new_builder()
+        .previous_output(mock_out_point(1))
+        .build();
+    let type_script = mock_cell().type_().to_opt().expect("type script");
+    let result = build_create_tx(&mut generator, input, type_script, mock_cell());
+    assert!(matches!(
+        result,
+        Err(TxBuilderError::NonEmptyInitialTree(_))
+    ));
+}
+
+#[test]
+fn update() {
+    let mut generator = build_generator();
+    let old_root = *generator.root();
+    generator.append_change(Bytes::copy_from_slice(&[10u8; 4]), None);
+    generator.append_change(
+        Bytes::copy_from_slice(&[25u8; 4]),
+        Some(Bytes::copy_from_slice(&[25u8; 20])),
+    );
+    let cell = mock_cell();
+    let tx = build_update_tx(
+        &mut generator,
+        cell.clone(),
+        old_root.as_slice(),
+        mock_out_point(2),
+    )
+    .expect("build update tx");
+
+    let input = tx.inputs().get(0).expect("input");
+    assert_eq!(input.previous_output(), mock_out_point(2));
+    let output = tx.output(0).expect("output");
+    assert_eq!(output.as_slice(), cell.as_slice());
+    let output_data = tx.outputs_data().get(0).expect("output data");
+    assert_eq!(output_data.raw_data().as_ref(), generator.root().as_slice());
+    let smt_update = extract_smt_update(&tx, 0, WitnessField::OutputType).expect("smt update");
+    assert!(smt_update.as_reader().verify_smt(&old_root).is_ok());
+}
+
+#[test]
+fn update_with_mismatched_root() {
+    let mut generator = build_generator();
+    generator.append_change(Bytes::copy_from_slice(&[10u8; 4]), None);
+    let result = build_update_tx(&mut generator, mock_cell(), &[0u8; 32], mock_out_point(2));
+    assert!(matches!(result, Err(TxBuilderError::MismatchedRoot { .. })));
+    let result = build_update_tx(&mut generator, mock_cell(), &[0u8; 31], mock_out_point(2));
+    assert!(matches!(result, Err(TxBuilderError::InvalidCellData(31))));
+    // Pending changes are not committed when failed.
+    assert!(generator.has_pending_changes());
+}
+
+#[test]
+fn destroy() {
+    let cell = mock_cell();
+    let tx = build_destroy_tx(cell.clone(), mock_out_point(3));
+    let output = tx.output(0).expect("output");
+    assert!(output.type_().to_opt().is_none());
+    assert_eq!(output.lock(), cell.lock());
+    assert_eq!(output.capacity(), cell.capacity());
+}
@@ -0,0 +1,37 @@
+use ckb_hash::blake2b_256;
+
+use crate::unique_id::calculate_unique_id;
+
+fn mock_input(seed: u8) -> [u8; 44] {
+    let mut input = [seed; 44];
+    // Use a zero `since`.
+    input[..8].copy_from_slice(&[0; 8]);
+    input
+}
+
+#[test]
+fn calculate() {
+    let input = mock_input(1);
+    for output_index in 0..4 {
+        let mut data = input.to_vec();
+        data.extend_from_slice(&(output_index as u64).to_le_bytes());
+        let expected = blake2b_256(&data);
+        let actual = calculate_unique_id(&input, output_index);
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn different_outputs() {
+    let input = mock_input(1);
+    let id0 = calculate_unique_id(&input, 0);
+    let id1 = calculate_unique_id(&input, 1);
+    assert_ne!(id0, id1);
+}
+
+#[test]
+fn different_inputs() {
+    let id0 = calculate_unique_id(&mock_input(1), 0);
+    let id1 = calculate_unique_id(&mock_input(2), 0);
+    assert_ne!(id0, id1);
+}
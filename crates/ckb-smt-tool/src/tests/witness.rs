@@ -0,0 +1,101 @@
+use alloc::vec;
+
+use ckb_types::{bytes::Bytes, core::TransactionBuilder, packed, prelude::*};
+
+use crate::{
+    error::WitnessError,
+    types::ProofGenerator,
+    witness::{
+        data_with_proof_witness_args, extract_data_with_proof, extract_smt_update,
+        smt_update_witness_args, WitnessField,
+    },
+};
+
+fn build_generator() -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = [i; 4];
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.update(&k, v).expect("smt update");
+    }
+    generator
+}
+
+#[test]
+fn smt_update_roundtrip() {
+    let mut generator = build_generator();
+    generator.append_change(Bytes::copy_from_slice(&[10u8; 4]), None);
+    let smt_update = generator.commit_changes().expect("smt commit");
+
+    for field in [
+        WitnessField::Lock,
+        WitnessField::InputType,
+        WitnessField::OutputType,
+    ] {
+        let witness_args = smt_update_witness_args(field, &smt_update);
+        let tx = TransactionBuilder::default()
+            .witness(Bytes::new().pack())
+            .witness(witness_args.as_bytes().pack())
+            .build();
+        let extracted = extract_smt_update(&tx, 1, field).expect("extract");
+        assert_eq!(extracted.as_slice(), smt_update.as_slice());
+    }
+}
+
+#[test]
+fn data_with_proof_roundtrip() {
+    let generator = build_generator();
+    let keys = vec![Bytes::copy_from_slice(&[5u8; 4])];
+    let data_with_proof = generator.data_with_proof(keys).expect("data with proof");
+
+    // Keep the signature in the lock field.
+    let signature = [1u8; 65];
+    let witness_args = {
+        let witness_args = data_with_proof_witness_args(WitnessField::InputType, &data_with_proof);
+        WitnessField::Lock.set(witness_args, &signature)
+    };
+    let tx = TransactionBuilder::default()
+        .witness(witness_args.as_bytes().pack())
+        .build();
+    let extracted = extract_data_with_proof(&tx, 0, WitnessField::InputType).expect("extract");
+    assert_eq!(extracted.as_slice(), data_with_proof.as_slice());
+    let lock = WitnessField::Lock.get(&witness_args).expect("lock");
+    assert_eq!(lock.raw_data().as_ref(), &signature);
+}
+
+#[test]
+fn extract_errors() {
+    let witness_args = WitnessField::Lock.set(packed::WitnessArgs::default(), &[0u8; 4]);
+    let tx = TransactionBuilder::default()
+        .witness(Bytes::from(vec![0u8; 3]).pack())
+        .witness(witness_args.as_bytes().pack())
+        .build();
+
+    let result = extract_smt_update(&tx, 2, WitnessField::Lock);
+    assert!(matches!(result, Err(WitnessError::NotExisted(2))));
+
+    let result = extract_smt_update(&tx, 0, WitnessField::Lock);
+    assert!(matches!(
+        result,
+        Err(WitnessError::InvalidWitnessArgs { index: 0, .. })
+    ));
+
+    let result = extract_smt_update(&tx, 1, WitnessField::OutputType);
+    assert!(matches!(
+        result,
+        Err(WitnessError::EmptyField {
+            index: 1,
+            field: WitnessField::OutputType
+        })
+    ));
+
+    let result = extract_data_with_proof(&tx, 1, WitnessField::Lock);
+    assert!(matches!(
+        result,
+        Err(WitnessError::InvalidField {
+            index: 1,
+            field: WitnessField::Lock,
+            ..
+        })
+    ));
+}
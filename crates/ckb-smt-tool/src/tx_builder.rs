@@ -0,0 +1,125 @@
+//! Build transaction skeletons for the KV-store cell.
+//!
+//! The KV-store cell is always put at the index 0 of inputs and outputs, and
+//! its `SmtUpdate` is put into the `output_type` field of the first witness.
+//! Other parts, such as cell deps, fee cells and signatures, should be
+//! completed by the callers.
+
+use ckb_types::{
+    bytes::Bytes,
+    core::{TransactionBuilder, TransactionView},
+    packed,
+    prelude::*,
+};
+
+use crate::{
+    error::TxBuilderError,
+    types::{verifier::hash_to_h256, ProofGenerator},
+    unique_id::calculate_unique_id,
+    witness::{smt_update_witness_args, WitnessField},
+};
+
+/// Builds a transaction to create a KV-store cell.
+///
+/// The args of `type_script` will be replaced by the unique ID, and the type
+/// of `output` will be replaced by the `type_script`.
+///
+/// The generator should have no committed leaves; if it has pending changes,
+/// they will be committed as the initial content.
+pub fn build_create_tx(
+    generator: &mut ProofGenerator,
+    first_input: packed::CellInput,
+    type_script: packed::Script,
+    output: packed::CellOutput,
+) -> Result<TransactionView, TxBuilderError> {
+    let output_index = 0;
+    let unique_id = calculate_unique_id(first_input.as_slice(), output_index);
+    let type_script = type_script
+        .as_builder()
+        .args(Bytes::copy_from_slice(&unique_id).pack())
+        .build();
+    let output = output.as_builder().type_(Some(type_script).pack()).build();
+
+    if !generator.root().is_zero() {
+        return Err(TxBuilderError::NonEmptyInitialTree(*generator.root()));
+    }
+    let builder = TransactionBuilder::default().input(first_input);
+    let builder = if generator.has_pending_changes() {
+        let smt_update = generator.commit_changes()?;
+        let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update);
+        builder.witness(witness.as_bytes().pack())
+    } else {
+        builder
+    };
+    let output_data = Bytes::copy_from_slice(generator.root().as_slice());
+
+    let tx = builder
+        .output(output)
+        .output_data(output_data.pack())
+        .build();
+    Ok(tx)
+}
+
+/// Builds a transaction to update a KV-store cell with all pending changes of
+/// the generator.
+///
+/// The root of the generator should be same as the data of the KV-store
+/// cell; the new KV-store cell keeps all fields of the current one, includes
+/// the capacity.
+///
+/// The pending changes are committed into the generator when the transaction
+/// is built, not when it is committed on chain. If the transaction may be
+/// dropped, write a snapshot of the generator before building it, see
+/// [`ProofGenerator::write_snapshot`], and read it back to roll back.
+pub fn build_update_tx(
+    generator: &mut ProofGenerator,
+    cell: packed::CellOutput,
+    cell_data: &[u8],
+    out_point: packed::OutPoint,
+) -> Result<TransactionView, TxBuilderError> {
+    check_root(generator, cell_data)?;
+    let smt_update = generator.commit_changes()?;
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update);
+    let input = packed::CellInput::new_builder()
+        .previous_output(out_point)
+        .build();
+    let output_data = Bytes::copy_from_slice(generator.root().as_slice());
+    let tx = TransactionBuilder::default()
+        .input(input)
+        .output(cell)
+        .output_data(output_data.pack())
+        .witness(witness.as_bytes().pack())
+        .build();
+    Ok(tx)
+}
+
+/// Builds a transaction to destroy a KV-store cell.
+///
+/// The capacity of the KV-store cell is returned to its lock, in a cell
+/// without type script and data.
+pub fn build_destroy_tx(cell: packed::CellOutput, out_point: packed::OutPoint) -> TransactionView {
+    let input = packed::CellInput::new_builder()
+        .previous_output(out_point)
+        .build();
+    let output = cell
+        .as_builder()
+        .type_(packed::ScriptOpt::default())
+        .build();
+    TransactionBuilder::default()
+        .input(input)
+        .output(output)
+        .output_data(Bytes::new().pack())
+        .build()
+}
+
+fn check_root(generator: &ProofGenerator, cell_data: &[u8]) -> Result<(), TxBuilderError> {
+    if cell_data.len() != 32 {
+        return Err(TxBuilderError::InvalidCellData(cell_data.len()));
+    }
+    let expected = hash_to_h256(cell_data);
+    if expected != *generator.root() {
+        let actual = *generator.root();
+        return Err(TxBuilderError::MismatchedRoot { expected, actual });
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Budgets to split a large commit into several updates.
+
+use molecule::prelude::*;
+
+use crate::types::{estimate::Estimate, generated::SmtUpdateReader};
+
+/// The limits of each update when the pending changes are committed by
+/// [`ProofGenerator::commit_changes_within`](crate::types::ProofGenerator::commit_changes_within).
+///
+/// A limit which is `None` is not checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitBudget {
+    /// The max size of the serialized `SmtUpdate`.
+    pub max_size: Option<usize>,
+    /// The max estimated cycles to verify the `SmtUpdate` on chain.
+    pub max_cycles: Option<u64>,
+}
+
+impl CommitBudget {
+    /// Creates a budget which only limits the size.
+    pub fn size(max_size: usize) -> Self {
+        Self {
+            max_size: Some(max_size),
+            max_cycles: None,
+        }
+    }
+
+    /// Creates a budget which only limits the estimated cycles.
+    pub fn cycles(max_cycles: u64) -> Self {
+        Self {
+            max_size: None,
+            max_cycles: Some(max_cycles),
+        }
+    }
+
+    /// Returns true if the update is within the budget.
+    pub fn allows(&self, update: SmtUpdateReader<'_>) -> bool {
+        let size_ok = self
+            .max_size
+            .map_or(true, |max_size| update.as_slice().len() <= max_size);
+        let cycles_ok = self.max_cycles.map_or(true, |max_cycles| {
+            Estimate::of_smt_update(update).cycles <= max_cycles
+        });
+        size_ok && cycles_ok
+    }
+}
@@ -0,0 +1,200 @@
+//! Merge and split `DataWithProof` without the full tree.
+//!
+//! All branches on the paths of the proven keys could be restored from a
+//! compiled proof, so a partial tree is rebuilt from the proofs, then the new
+//! proof is generated from the partial tree.
+
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use ckb_hash::blake2b_256;
+use molecule::bytes::Bytes;
+use sparse_merkle_tree::{
+    blake2b::Blake2bHasher,
+    default_store::DefaultStore,
+    error::Error as SmtLibError,
+    merge::{merge, MergeValue},
+    traits::StoreWriteOps,
+    BranchKey, BranchNode, H256,
+};
+
+use crate::{
+    error::GeneratorError,
+    types::{
+        generated::{DataWithProof, DataWithProofReader},
+        prover::{data_with_proof_from, BytesOpt, Smt},
+        verifier::hash_to_h256,
+    },
+};
+
+/// Merges several `DataWithProof` for the same root into one.
+///
+/// All of them are verified with the root at first; the duplicated keys are
+/// only kept once, and the keys are kept in the order of their first
+/// occurrences.
+pub fn merge_data_with_proofs(
+    root: &H256,
+    items: &[DataWithProofReader<'_>],
+) -> Result<DataWithProof, GeneratorError> {
+    let mut store = DefaultStore::default();
+    let mut seen = BTreeSet::new();
+    let mut keys: Vec<Bytes> = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        restore(&mut store, root, index, *item)?;
+        for kv in item.data().iter() {
+            let key = Bytes::copy_from_slice(kv.key().raw_data());
+            if seen.insert(key.clone()) {
+                keys.push(key);
+            }
+        }
+    }
+    let smt = Smt::new(*root, store);
+    data_with_proof_from(&smt, keys)
+}
+
+/// Splits a `DataWithProof` for a subset of the keys out.
+///
+/// The input is verified with the root at first, and all keys should be in
+/// it.
+pub fn split_data_with_proof(
+    root: &H256,
+    item: DataWithProofReader<'_>,
+    keys: Vec<Bytes>,
+) -> Result<DataWithProof, GeneratorError> {
+    let mut store = DefaultStore::default();
+    restore(&mut store, root, 0, item)?;
+    let proven: BTreeSet<_> = item
+        .data()
+        .iter()
+        .map(|kv| Bytes::copy_from_slice(kv.key().raw_data()))
+        .collect();
+    for key in &keys {
+        if !proven.contains(key) {
+            return Err(GeneratorError::KeyNotProven(key.clone()));
+        }
+    }
+    let smt = Smt::new(*root, store);
+    data_with_proof_from(&smt, keys)
+}
+
+// Verifies the item, then restores its leaves and all branches on their paths
+// into the store.
+fn restore(
+    store: &mut DefaultStore<BytesOpt>,
+    root: &H256,
+    index: usize,
+    item: DataWithProofReader<'_>,
+) -> Result<(), GeneratorError> {
+    item.verify_smt(root)
+        .map_err(|error| GeneratorError::InvalidDataWithProof { index, error })?;
+
+    let mut leaves = Vec::new();
+    for kv in item.data().iter() {
+        let key: H256 = blake2b_256(kv.key().raw_data()).into();
+        let value = kv
+            .value()
+            .to_opt()
+            .map(|v| Bytes::copy_from_slice(v.raw_data()));
+        let value_h256 = value
+            .as_ref()
+            .map_or_else(H256::zero, |v| blake2b_256(v).into());
+        if let Some(value) = value {
+            store.insert_leaf(key, BytesOpt(Some(value)))?;
+        }
+        leaves.push((key, value_h256));
+    }
+    restore_branches(store, item.proof().raw_data(), leaves)
+}
+
+// Runs the compiled proof as `CompiledMerkleProof::compute_root`, and records
+// each branch when its children are merged.
+//
+// The proof is already verified, so all errors here are unexpected.
+fn restore_branches(
+    store: &mut DefaultStore<BytesOpt>,
+    proof: &[u8],
+    mut leaves: Vec<(H256, H256)>,
+) -> Result<(), GeneratorError> {
+    let corrupted = || GeneratorError::SmtLib(SmtLibError::CorruptedProof);
+
+    leaves.sort_unstable_by_key(|(key, _)| *key);
+    let mut leaves = leaves.into_iter();
+    let mut stack: Vec<(u16, H256, MergeValue)> = Vec::new();
+    let mut index = 0;
+    while index < proof.len() {
+        let code = proof[index];
+        index += 1;
+        match code {
+            // L: push a leaf.
+            0x4C => {
+                let (key, value) = leaves.next().ok_or_else(corrupted)?;
+                stack.push((0, key, MergeValue::from_h256(value)));
+            }
+            // P and Q: merge with a sibling in the proof.
+            0x50 | 0x51 => {
+                let size = if code == 0x50 { 32 } else { 65 };
+                let data = proof.get(index..index + size).ok_or_else(corrupted)?;
+                index += size;
+                let sibling = if code == 0x50 {
+                    MergeValue::from_h256(hash_to_h256(data))
+                } else {
+                    MergeValue::MergeWithZero {
+                        zero_count: data[0],
+                        base_node: hash_to_h256(&data[1..33]),
+                        zero_bits: hash_to_h256(&data[33..65]),
+                    }
+                };
+                let (height, key, value) = stack.pop().ok_or_else(corrupted)?;
+                let height = u8::try_from(height).map_err(|_| corrupted())?;
+                let parent = record(store, height, &key, value, sibling)?;
+                stack.push((u16::from(height) + 1, key.parent_path(height), parent));
+            }
+            // H: merge the top two items in the stack.
+            0x48 => {
+                let (_, key_b, value_b) = stack.pop().ok_or_else(corrupted)?;
+                let (height, key_a, value_a) = stack.pop().ok_or_else(corrupted)?;
+                let height = u8::try_from(height).map_err(|_| corrupted())?;
+                let parent = record(store, height, &key_a, value_a, value_b)?;
+                stack.push((u16::from(height) + 1, key_b.parent_path(height), parent));
+            }
+            // O: merge with zeros for n times.
+            0x4F => {
+                let n = *proof.get(index).ok_or_else(corrupted)?;
+                index += 1;
+                let count: u16 = if n == 0 { 256 } else { n.into() };
+                let (base_height, key, mut value) = stack.pop().ok_or_else(corrupted)?;
+                let mut parent_key = key;
+                for height in base_height..base_height + count {
+                    let height = u8::try_from(height).map_err(|_| corrupted())?;
+                    value = record(store, height, &key, value, MergeValue::zero())?;
+                    parent_key = key.parent_path(height);
+                }
+                stack.push((base_height + count, parent_key, value));
+            }
+            _ => return Err(GeneratorError::SmtLib(SmtLibError::InvalidCode(code))),
+        }
+    }
+    Ok(())
+}
+
+// Records the branch which has the node and its sibling as children, then
+// returns the merged parent.
+fn record(
+    store: &mut DefaultStore<BytesOpt>,
+    height: u8,
+    key: &H256,
+    node: MergeValue,
+    sibling: MergeValue,
+) -> Result<MergeValue, GeneratorError> {
+    let parent_key = key.parent_path(height);
+    let (left, right) = if key.is_right(height) {
+        (sibling, node)
+    } else {
+        (node, sibling)
+    };
+    let parent = merge::<Blake2bHasher>(height, &parent_key, &left, &right);
+    if !left.is_zero() || !right.is_zero() {
+        let branch_key = BranchKey::new(height, parent_key);
+        store.insert_branch(branch_key, BranchNode { left, right })?;
+    }
+    Ok(parent)
+}
@@ -1899,8 +1899,8 @@ impl molecule::prelude::Builder for SmtUpdateBuilder {
     }
 }
 #[derive(Clone)]
-pub struct KeyValue(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for KeyValue {
+pub struct SmtUpdateChain(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SmtUpdateChain {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -1909,73 +1909,76 @@ impl ::core::fmt::LowerHex for KeyValue {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for KeyValue {
+impl ::core::fmt::Debug for SmtUpdateChain {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for KeyValue {
+impl ::core::fmt::Display for SmtUpdateChain {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "key", self.key())?;
-        write!(f, ", {}: {}", "value", self.value())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl ::core::default::Default for KeyValue {
+impl ::core::default::Default for SmtUpdateChain {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        KeyValue::new_unchecked(v)
+        SmtUpdateChain::new_unchecked(v)
     }
 }
-impl KeyValue {
-    const DEFAULT_VALUE: [u8; 16] = [16, 0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0];
-    pub const FIELD_COUNT: usize = 2;
+impl SmtUpdateChain {
+    const DEFAULT_VALUE: [u8; 4] = [4, 0, 0, 0];
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn field_count(&self) -> usize {
+    pub fn item_count(&self) -> usize {
         if self.total_size() == molecule::NUMBER_SIZE {
             0
         } else {
             (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
         }
     }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn key(&self) -> Bytes {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        Bytes::new_unchecked(self.0.slice(start..end))
+    pub fn get(&self, idx: usize) -> Option<SmtUpdate> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
     }
-    pub fn value(&self) -> BytesOpt {
+    pub fn get_unchecked(&self, idx: usize) -> SmtUpdate {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
-            BytesOpt::new_unchecked(self.0.slice(start..end))
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            SmtUpdate::new_unchecked(self.0.slice(start..))
         } else {
-            BytesOpt::new_unchecked(self.0.slice(start..))
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            SmtUpdate::new_unchecked(self.0.slice(start..end))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> KeyValueReader<'r> {
-        KeyValueReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> SmtUpdateChainReader<'r> {
+        SmtUpdateChainReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for KeyValue {
-    type Builder = KeyValueBuilder;
-    const NAME: &'static str = "KeyValue";
+impl molecule::prelude::Entity for SmtUpdateChain {
+    type Builder = SmtUpdateChainBuilder;
+    const NAME: &'static str = "SmtUpdateChain";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        KeyValue(data)
+        SmtUpdateChain(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -1984,21 +1987,21 @@ impl molecule::prelude::Entity for KeyValue {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        KeyValueReader::from_slice(slice).map(|reader| reader.to_entity())
+        SmtUpdateChainReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        KeyValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SmtUpdateChainReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().key(self.key()).value(self.value())
+        Self::new_builder().extend(self.into_iter())
     }
 }
 #[derive(Clone, Copy)]
-pub struct KeyValueReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for KeyValueReader<'r> {
+pub struct SmtUpdateChainReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SmtUpdateChainReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2007,66 +2010,69 @@ impl<'r> ::core::fmt::LowerHex for KeyValueReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for KeyValueReader<'r> {
+impl<'r> ::core::fmt::Debug for SmtUpdateChainReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for KeyValueReader<'r> {
+impl<'r> ::core::fmt::Display for SmtUpdateChainReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "key", self.key())?;
-        write!(f, ", {}: {}", "value", self.value())?;
-        let extra_count = self.count_extra_fields();
-        if extra_count != 0 {
-            write!(f, ", .. ({} fields)", extra_count)?;
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
         }
-        write!(f, " }}")
+        write!(f, "]")
     }
 }
-impl<'r> KeyValueReader<'r> {
-    pub const FIELD_COUNT: usize = 2;
+impl<'r> SmtUpdateChainReader<'r> {
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn field_count(&self) -> usize {
+    pub fn item_count(&self) -> usize {
         if self.total_size() == molecule::NUMBER_SIZE {
             0
         } else {
             (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
         }
     }
-    pub fn count_extra_fields(&self) -> usize {
-        self.field_count() - Self::FIELD_COUNT
+    pub fn len(&self) -> usize {
+        self.item_count()
     }
-    pub fn has_extra_fields(&self) -> bool {
-        Self::FIELD_COUNT != self.field_count()
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
-    pub fn key(&self) -> BytesReader<'r> {
-        let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[4..]) as usize;
-        let end = molecule::unpack_number(&slice[8..]) as usize;
-        BytesReader::new_unchecked(&self.as_slice()[start..end])
+    pub fn get(&self, idx: usize) -> Option<SmtUpdateReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
     }
-    pub fn value(&self) -> BytesOptReader<'r> {
+    pub fn get_unchecked(&self, idx: usize) -> SmtUpdateReader<'r> {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
-        if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
-            BytesOptReader::new_unchecked(&self.as_slice()[start..end])
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            SmtUpdateReader::new_unchecked(&self.as_slice()[start..])
         } else {
-            BytesOptReader::new_unchecked(&self.as_slice()[start..])
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            SmtUpdateReader::new_unchecked(&self.as_slice()[start..end])
         }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for KeyValueReader<'r> {
-    type Entity = KeyValue;
-    const NAME: &'static str = "KeyValueReader";
+impl<'r> molecule::prelude::Reader<'r> for SmtUpdateChainReader<'r> {
+    type Entity = SmtUpdateChain;
+    const NAME: &'static str = "SmtUpdateChainReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        KeyValueReader(slice)
+        SmtUpdateChainReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -2081,8 +2087,16 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValueReader<'r> {
         if slice_len != total_size {
             return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
         }
+        if slice_len == molecule::NUMBER_SIZE {
+            return Ok(());
+        }
         if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+            return ve!(
+                Self,
+                TotalSizeNotMatch,
+                molecule::NUMBER_SIZE * 2,
+                slice_len
+            );
         }
         let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
         if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
@@ -2091,12 +2105,6 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValueReader<'r> {
         if slice_len < offset_first {
             return ve!(Self, HeaderIsBroken, offset_first, slice_len);
         }
-        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
-        if field_count < Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        } else if !compatible && field_count > Self::FIELD_COUNT {
-            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
-        };
         let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
             .chunks_exact(molecule::NUMBER_SIZE)
             .map(|x| molecule::unpack_number(x) as usize)
@@ -2105,60 +2113,134 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValueReader<'r> {
         if offsets.windows(2).any(|i| i[0] > i[1]) {
             return ve!(Self, OffsetsNotMatch);
         }
-        BytesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        BytesOptReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        for pair in offsets.windows(2) {
+            let start = pair[0];
+            let end = pair[1];
+            SmtUpdateReader::verify(&slice[start..end], compatible)?;
+        }
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct KeyValueBuilder {
-    pub(crate) key: Bytes,
-    pub(crate) value: BytesOpt,
-}
-impl KeyValueBuilder {
-    pub const FIELD_COUNT: usize = 2;
-    pub fn key(mut self, v: Bytes) -> Self {
-        self.key = v;
+pub struct SmtUpdateChainBuilder(pub(crate) Vec<SmtUpdate>);
+impl SmtUpdateChainBuilder {
+    pub fn set(mut self, v: Vec<SmtUpdate>) -> Self {
+        self.0 = v;
         self
     }
-    pub fn value(mut self, v: BytesOpt) -> Self {
-        self.value = v;
+    pub fn push(mut self, v: SmtUpdate) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = SmtUpdate>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
         self
     }
+    pub fn replace(&mut self, index: usize, v: SmtUpdate) -> Option<SmtUpdate> {
+        self.0
+            .get_mut(index)
+            .map(|item| ::core::mem::replace(item, v))
+    }
 }
-impl molecule::prelude::Builder for KeyValueBuilder {
-    type Entity = KeyValue;
-    const NAME: &'static str = "KeyValueBuilder";
+impl molecule::prelude::Builder for SmtUpdateChainBuilder {
+    type Entity = SmtUpdateChain;
+    const NAME: &'static str = "SmtUpdateChainBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.key.as_slice().len()
-            + self.value.as_slice().len()
+        molecule::NUMBER_SIZE * (self.0.len() + 1)
+            + self
+                .0
+                .iter()
+                .map(|inner| inner.as_slice().len())
+                .sum::<usize>()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
-        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
-        offsets.push(total_size);
-        total_size += self.key.as_slice().len();
-        offsets.push(total_size);
-        total_size += self.value.as_slice().len();
-        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-        for offset in offsets.into_iter() {
-            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        let item_count = self.0.len();
+        if item_count == 0 {
+            writer.write_all(&molecule::pack_number(
+                molecule::NUMBER_SIZE as molecule::Number,
+            ))?;
+        } else {
+            let (total_size, offsets) = self.0.iter().fold(
+                (
+                    molecule::NUMBER_SIZE * (item_count + 1),
+                    Vec::with_capacity(item_count),
+                ),
+                |(start, mut offsets), inner| {
+                    offsets.push(start);
+                    (start + inner.as_slice().len(), offsets)
+                },
+            );
+            writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+            for offset in offsets.into_iter() {
+                writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+            }
+            for inner in self.0.iter() {
+                writer.write_all(inner.as_slice())?;
+            }
         }
-        writer.write_all(self.key.as_slice())?;
-        writer.write_all(self.value.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        KeyValue::new_unchecked(inner.into())
+        SmtUpdateChain::new_unchecked(inner.into())
+    }
+}
+pub struct SmtUpdateChainIterator(SmtUpdateChain, usize, usize);
+impl ::core::iter::Iterator for SmtUpdateChainIterator {
+    type Item = SmtUpdate;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for SmtUpdateChainIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for SmtUpdateChain {
+    type Item = SmtUpdate;
+    type IntoIter = SmtUpdateChainIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        SmtUpdateChainIterator(self, 0, len)
+    }
+}
+impl<'r> SmtUpdateChainReader<'r> {
+    pub fn iter<'t>(&'t self) -> SmtUpdateChainReaderIterator<'t, 'r> {
+        SmtUpdateChainReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct SmtUpdateChainReaderIterator<'t, 'r>(&'t SmtUpdateChainReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for SmtUpdateChainReaderIterator<'t, 'r> {
+    type Item = SmtUpdateReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for SmtUpdateChainReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
     }
 }
 #[derive(Clone)]
-pub struct KeyValues(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for KeyValues {
+pub struct KeyValue(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for KeyValue {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2167,76 +2249,73 @@ impl ::core::fmt::LowerHex for KeyValues {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for KeyValues {
+impl ::core::fmt::Debug for KeyValue {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for KeyValues {
+impl ::core::fmt::Display for KeyValue {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-        write!(f, "{} [", Self::NAME)?;
-        for i in 0..self.len() {
-            if i == 0 {
-                write!(f, "{}", self.get_unchecked(i))?;
-            } else {
-                write!(f, ", {}", self.get_unchecked(i))?;
-            }
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "key", self.key())?;
+        write!(f, ", {}: {}", "value", self.value())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
         }
-        write!(f, "]")
+        write!(f, " }}")
     }
 }
-impl ::core::default::Default for KeyValues {
+impl ::core::default::Default for KeyValue {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        KeyValues::new_unchecked(v)
+        KeyValue::new_unchecked(v)
     }
 }
-impl KeyValues {
-    const DEFAULT_VALUE: [u8; 4] = [4, 0, 0, 0];
+impl KeyValue {
+    const DEFAULT_VALUE: [u8; 16] = [16, 0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 0, 0, 0, 0];
+    pub const FIELD_COUNT: usize = 2;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
-    pub fn item_count(&self) -> usize {
+    pub fn field_count(&self) -> usize {
         if self.total_size() == molecule::NUMBER_SIZE {
             0
         } else {
             (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
         }
     }
-    pub fn len(&self) -> usize {
-        self.item_count()
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
     }
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
     }
-    pub fn get(&self, idx: usize) -> Option<KeyValue> {
-        if idx >= self.len() {
-            None
-        } else {
-            Some(self.get_unchecked(idx))
-        }
+    pub fn key(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        Bytes::new_unchecked(self.0.slice(start..end))
     }
-    pub fn get_unchecked(&self, idx: usize) -> KeyValue {
+    pub fn value(&self) -> BytesOpt {
         let slice = self.as_slice();
-        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
-        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
-        if idx == self.len() - 1 {
-            KeyValue::new_unchecked(self.0.slice(start..))
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            BytesOpt::new_unchecked(self.0.slice(start..end))
         } else {
-            let end_idx = start_idx + molecule::NUMBER_SIZE;
-            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
-            KeyValue::new_unchecked(self.0.slice(start..end))
+            BytesOpt::new_unchecked(self.0.slice(start..))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> KeyValuesReader<'r> {
-        KeyValuesReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> KeyValueReader<'r> {
+        KeyValueReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for KeyValues {
-    type Builder = KeyValuesBuilder;
-    const NAME: &'static str = "KeyValues";
+impl molecule::prelude::Entity for KeyValue {
+    type Builder = KeyValueBuilder;
+    const NAME: &'static str = "KeyValue";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        KeyValues(data)
+        KeyValue(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -2245,21 +2324,21 @@ impl molecule::prelude::Entity for KeyValues {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        KeyValuesReader::from_slice(slice).map(|reader| reader.to_entity())
+        KeyValueReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        KeyValuesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        KeyValueReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().extend(self.into_iter())
+        Self::new_builder().key(self.key()).value(self.value())
     }
 }
 #[derive(Clone, Copy)]
-pub struct KeyValuesReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for KeyValuesReader<'r> {
+pub struct KeyValueReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for KeyValueReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2268,12 +2347,172 @@ impl<'r> ::core::fmt::LowerHex for KeyValuesReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for KeyValuesReader<'r> {
+impl<'r> ::core::fmt::Debug for KeyValueReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for KeyValuesReader<'r> {
+impl<'r> ::core::fmt::Display for KeyValueReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "key", self.key())?;
+        write!(f, ", {}: {}", "value", self.value())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> KeyValueReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn key(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        BytesReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn value(&self) -> BytesOptReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            BytesOptReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesOptReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for KeyValueReader<'r> {
+    type Entity = KeyValue;
+    const NAME: &'static str = "KeyValueReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        KeyValueReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        BytesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        BytesOptReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct KeyValueBuilder {
+    pub(crate) key: Bytes,
+    pub(crate) value: BytesOpt,
+}
+impl KeyValueBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn key(mut self, v: Bytes) -> Self {
+        self.key = v;
+        self
+    }
+    pub fn value(mut self, v: BytesOpt) -> Self {
+        self.value = v;
+        self
+    }
+}
+impl molecule::prelude::Builder for KeyValueBuilder {
+    type Entity = KeyValue;
+    const NAME: &'static str = "KeyValueBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.key.as_slice().len()
+            + self.value.as_slice().len()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.key.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.value.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+        }
+        writer.write_all(self.key.as_slice())?;
+        writer.write_all(self.value.as_slice())?;
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        KeyValue::new_unchecked(inner.into())
+    }
+}
+#[derive(Clone)]
+pub struct KeyValues(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for KeyValues {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for KeyValues {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for KeyValues {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} [", Self::NAME)?;
         for i in 0..self.len() {
@@ -2286,7 +2525,14 @@ impl<'r> ::core::fmt::Display for KeyValuesReader<'r> {
         write!(f, "]")
     }
 }
-impl<'r> KeyValuesReader<'r> {
+impl ::core::default::Default for KeyValues {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        KeyValues::new_unchecked(v)
+    }
+}
+impl KeyValues {
+    const DEFAULT_VALUE: [u8; 4] = [4, 0, 0, 0];
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2303,14 +2549,108 @@ impl<'r> KeyValuesReader<'r> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
-    pub fn get(&self, idx: usize) -> Option<KeyValueReader<'r>> {
+    pub fn get(&self, idx: usize) -> Option<KeyValue> {
         if idx >= self.len() {
             None
         } else {
             Some(self.get_unchecked(idx))
         }
     }
-    pub fn get_unchecked(&self, idx: usize) -> KeyValueReader<'r> {
+    pub fn get_unchecked(&self, idx: usize) -> KeyValue {
+        let slice = self.as_slice();
+        let start_idx = molecule::NUMBER_SIZE * (1 + idx);
+        let start = molecule::unpack_number(&slice[start_idx..]) as usize;
+        if idx == self.len() - 1 {
+            KeyValue::new_unchecked(self.0.slice(start..))
+        } else {
+            let end_idx = start_idx + molecule::NUMBER_SIZE;
+            let end = molecule::unpack_number(&slice[end_idx..]) as usize;
+            KeyValue::new_unchecked(self.0.slice(start..end))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> KeyValuesReader<'r> {
+        KeyValuesReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for KeyValues {
+    type Builder = KeyValuesBuilder;
+    const NAME: &'static str = "KeyValues";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        KeyValues(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        KeyValuesReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        KeyValuesReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().extend(self.into_iter())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct KeyValuesReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for KeyValuesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for KeyValuesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for KeyValuesReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} [", Self::NAME)?;
+        for i in 0..self.len() {
+            if i == 0 {
+                write!(f, "{}", self.get_unchecked(i))?;
+            } else {
+                write!(f, ", {}", self.get_unchecked(i))?;
+            }
+        }
+        write!(f, "]")
+    }
+}
+impl<'r> KeyValuesReader<'r> {
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn item_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.item_count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn get(&self, idx: usize) -> Option<KeyValueReader<'r>> {
+        if idx >= self.len() {
+            None
+        } else {
+            Some(self.get_unchecked(idx))
+        }
+    }
+    pub fn get_unchecked(&self, idx: usize) -> KeyValueReader<'r> {
         let slice = self.as_slice();
         let start_idx = molecule::NUMBER_SIZE * (1 + idx);
         let start = molecule::unpack_number(&slice[start_idx..]) as usize;
@@ -2330,7 +2670,343 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValuesReader<'r> {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        KeyValuesReader(slice)
+        KeyValuesReader(slice)
+    }
+    fn as_slice(&self) -> &'r [u8] {
+        self.0
+    }
+    fn verify(slice: &[u8], compatible: bool) -> molecule::error::VerificationResult<()> {
+        use molecule::verification_error as ve;
+        let slice_len = slice.len();
+        if slice_len < molecule::NUMBER_SIZE {
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE, slice_len);
+        }
+        let total_size = molecule::unpack_number(slice) as usize;
+        if slice_len != total_size {
+            return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
+        }
+        if slice_len == molecule::NUMBER_SIZE {
+            return Ok(());
+        }
+        if slice_len < molecule::NUMBER_SIZE * 2 {
+            return ve!(
+                Self,
+                TotalSizeNotMatch,
+                molecule::NUMBER_SIZE * 2,
+                slice_len
+            );
+        }
+        let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
+        if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        if slice_len < offset_first {
+            return ve!(Self, HeaderIsBroken, offset_first, slice_len);
+        }
+        let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
+            .chunks_exact(molecule::NUMBER_SIZE)
+            .map(|x| molecule::unpack_number(x) as usize)
+            .collect();
+        offsets.push(total_size);
+        if offsets.windows(2).any(|i| i[0] > i[1]) {
+            return ve!(Self, OffsetsNotMatch);
+        }
+        for pair in offsets.windows(2) {
+            let start = pair[0];
+            let end = pair[1];
+            KeyValueReader::verify(&slice[start..end], compatible)?;
+        }
+        Ok(())
+    }
+}
+#[derive(Debug, Default)]
+pub struct KeyValuesBuilder(pub(crate) Vec<KeyValue>);
+impl KeyValuesBuilder {
+    pub fn set(mut self, v: Vec<KeyValue>) -> Self {
+        self.0 = v;
+        self
+    }
+    pub fn push(mut self, v: KeyValue) -> Self {
+        self.0.push(v);
+        self
+    }
+    pub fn extend<T: ::core::iter::IntoIterator<Item = KeyValue>>(mut self, iter: T) -> Self {
+        for elem in iter {
+            self.0.push(elem);
+        }
+        self
+    }
+    pub fn replace(&mut self, index: usize, v: KeyValue) -> Option<KeyValue> {
+        self.0
+            .get_mut(index)
+            .map(|item| ::core::mem::replace(item, v))
+    }
+}
+impl molecule::prelude::Builder for KeyValuesBuilder {
+    type Entity = KeyValues;
+    const NAME: &'static str = "KeyValuesBuilder";
+    fn expected_length(&self) -> usize {
+        molecule::NUMBER_SIZE * (self.0.len() + 1)
+            + self
+                .0
+                .iter()
+                .map(|inner| inner.as_slice().len())
+                .sum::<usize>()
+    }
+    fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
+        let item_count = self.0.len();
+        if item_count == 0 {
+            writer.write_all(&molecule::pack_number(
+                molecule::NUMBER_SIZE as molecule::Number,
+            ))?;
+        } else {
+            let (total_size, offsets) = self.0.iter().fold(
+                (
+                    molecule::NUMBER_SIZE * (item_count + 1),
+                    Vec::with_capacity(item_count),
+                ),
+                |(start, mut offsets), inner| {
+                    offsets.push(start);
+                    (start + inner.as_slice().len(), offsets)
+                },
+            );
+            writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+            for offset in offsets.into_iter() {
+                writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
+            }
+            for inner in self.0.iter() {
+                writer.write_all(inner.as_slice())?;
+            }
+        }
+        Ok(())
+    }
+    fn build(&self) -> Self::Entity {
+        let mut inner = Vec::with_capacity(self.expected_length());
+        self.write(&mut inner)
+            .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
+        KeyValues::new_unchecked(inner.into())
+    }
+}
+pub struct KeyValuesIterator(KeyValues, usize, usize);
+impl ::core::iter::Iterator for KeyValuesIterator {
+    type Item = KeyValue;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl ::core::iter::ExactSizeIterator for KeyValuesIterator {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+impl ::core::iter::IntoIterator for KeyValues {
+    type Item = KeyValue;
+    type IntoIter = KeyValuesIterator;
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+        KeyValuesIterator(self, 0, len)
+    }
+}
+impl<'r> KeyValuesReader<'r> {
+    pub fn iter<'t>(&'t self) -> KeyValuesReaderIterator<'t, 'r> {
+        KeyValuesReaderIterator(&self, 0, self.len())
+    }
+}
+pub struct KeyValuesReaderIterator<'t, 'r>(&'t KeyValuesReader<'r>, usize, usize);
+impl<'t: 'r, 'r> ::core::iter::Iterator for KeyValuesReaderIterator<'t, 'r> {
+    type Item = KeyValueReader<'t>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.1 >= self.2 {
+            None
+        } else {
+            let ret = self.0.get_unchecked(self.1);
+            self.1 += 1;
+            Some(ret)
+        }
+    }
+}
+impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for KeyValuesReaderIterator<'t, 'r> {
+    fn len(&self) -> usize {
+        self.2 - self.1
+    }
+}
+#[derive(Clone)]
+pub struct DataWithProof(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for DataWithProof {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl ::core::fmt::Debug for DataWithProof {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl ::core::fmt::Display for DataWithProof {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "data", self.data())?;
+        write!(f, ", {}: {}", "proof", self.proof())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl ::core::default::Default for DataWithProof {
+    fn default() -> Self {
+        let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
+        DataWithProof::new_unchecked(v)
+    }
+}
+impl DataWithProof {
+    const DEFAULT_VALUE: [u8; 20] = [
+        20, 0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn data(&self) -> KeyValues {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        KeyValues::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn proof(&self) -> Bytes {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            Bytes::new_unchecked(self.0.slice(start..end))
+        } else {
+            Bytes::new_unchecked(self.0.slice(start..))
+        }
+    }
+    pub fn as_reader<'r>(&'r self) -> DataWithProofReader<'r> {
+        DataWithProofReader::new_unchecked(self.as_slice())
+    }
+}
+impl molecule::prelude::Entity for DataWithProof {
+    type Builder = DataWithProofBuilder;
+    const NAME: &'static str = "DataWithProof";
+    fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
+        DataWithProof(data)
+    }
+    fn as_bytes(&self) -> molecule::bytes::Bytes {
+        self.0.clone()
+    }
+    fn as_slice(&self) -> &[u8] {
+        &self.0[..]
+    }
+    fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        DataWithProofReader::from_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
+        DataWithProofReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+    }
+    fn new_builder() -> Self::Builder {
+        ::core::default::Default::default()
+    }
+    fn as_builder(self) -> Self::Builder {
+        Self::new_builder().data(self.data()).proof(self.proof())
+    }
+}
+#[derive(Clone, Copy)]
+pub struct DataWithProofReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for DataWithProofReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        use molecule::hex_string;
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        write!(f, "{}", hex_string(self.as_slice()))
+    }
+}
+impl<'r> ::core::fmt::Debug for DataWithProofReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{}({:#x})", Self::NAME, self)
+    }
+}
+impl<'r> ::core::fmt::Display for DataWithProofReader<'r> {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "{} {{ ", Self::NAME)?;
+        write!(f, "{}: {}", "data", self.data())?;
+        write!(f, ", {}: {}", "proof", self.proof())?;
+        let extra_count = self.count_extra_fields();
+        if extra_count != 0 {
+            write!(f, ", .. ({} fields)", extra_count)?;
+        }
+        write!(f, " }}")
+    }
+}
+impl<'r> DataWithProofReader<'r> {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn total_size(&self) -> usize {
+        molecule::unpack_number(self.as_slice()) as usize
+    }
+    pub fn field_count(&self) -> usize {
+        if self.total_size() == molecule::NUMBER_SIZE {
+            0
+        } else {
+            (molecule::unpack_number(&self.as_slice()[molecule::NUMBER_SIZE..]) as usize / 4) - 1
+        }
+    }
+    pub fn count_extra_fields(&self) -> usize {
+        self.field_count() - Self::FIELD_COUNT
+    }
+    pub fn has_extra_fields(&self) -> bool {
+        Self::FIELD_COUNT != self.field_count()
+    }
+    pub fn data(&self) -> KeyValuesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[4..]) as usize;
+        let end = molecule::unpack_number(&slice[8..]) as usize;
+        KeyValuesReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn proof(&self) -> BytesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        if self.has_extra_fields() {
+            let end = molecule::unpack_number(&slice[12..]) as usize;
+            BytesReader::new_unchecked(&self.as_slice()[start..end])
+        } else {
+            BytesReader::new_unchecked(&self.as_slice()[start..])
+        }
+    }
+}
+impl<'r> molecule::prelude::Reader<'r> for DataWithProofReader<'r> {
+    type Entity = DataWithProof;
+    const NAME: &'static str = "DataWithProofReader";
+    fn to_entity(&self) -> Self::Entity {
+        Self::Entity::new_unchecked(self.as_slice().to_owned().into())
+    }
+    fn new_unchecked(slice: &'r [u8]) -> Self {
+        DataWithProofReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -2345,16 +3021,8 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValuesReader<'r> {
         if slice_len != total_size {
             return ve!(Self, TotalSizeNotMatch, total_size, slice_len);
         }
-        if slice_len == molecule::NUMBER_SIZE {
-            return Ok(());
-        }
         if slice_len < molecule::NUMBER_SIZE * 2 {
-            return ve!(
-                Self,
-                TotalSizeNotMatch,
-                molecule::NUMBER_SIZE * 2,
-                slice_len
-            );
+            return ve!(Self, HeaderIsBroken, molecule::NUMBER_SIZE * 2, slice_len);
         }
         let offset_first = molecule::unpack_number(&slice[molecule::NUMBER_SIZE..]) as usize;
         if offset_first % molecule::NUMBER_SIZE != 0 || offset_first < molecule::NUMBER_SIZE * 2 {
@@ -2363,6 +3031,12 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValuesReader<'r> {
         if slice_len < offset_first {
             return ve!(Self, HeaderIsBroken, offset_first, slice_len);
         }
+        let field_count = offset_first / molecule::NUMBER_SIZE - 1;
+        if field_count < Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        } else if !compatible && field_count > Self::FIELD_COUNT {
+            return ve!(Self, FieldCountNotMatch, Self::FIELD_COUNT, field_count);
+        };
         let mut offsets: Vec<usize> = slice[molecule::NUMBER_SIZE..offset_first]
             .chunks_exact(molecule::NUMBER_SIZE)
             .map(|x| molecule::unpack_number(x) as usize)
@@ -2371,134 +3045,60 @@ impl<'r> molecule::prelude::Reader<'r> for KeyValuesReader<'r> {
         if offsets.windows(2).any(|i| i[0] > i[1]) {
             return ve!(Self, OffsetsNotMatch);
         }
-        for pair in offsets.windows(2) {
-            let start = pair[0];
-            let end = pair[1];
-            KeyValueReader::verify(&slice[start..end], compatible)?;
-        }
+        KeyValuesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        BytesReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct KeyValuesBuilder(pub(crate) Vec<KeyValue>);
-impl KeyValuesBuilder {
-    pub fn set(mut self, v: Vec<KeyValue>) -> Self {
-        self.0 = v;
-        self
-    }
-    pub fn push(mut self, v: KeyValue) -> Self {
-        self.0.push(v);
+pub struct DataWithProofBuilder {
+    pub(crate) data: KeyValues,
+    pub(crate) proof: Bytes,
+}
+impl DataWithProofBuilder {
+    pub const FIELD_COUNT: usize = 2;
+    pub fn data(mut self, v: KeyValues) -> Self {
+        self.data = v;
         self
     }
-    pub fn extend<T: ::core::iter::IntoIterator<Item = KeyValue>>(mut self, iter: T) -> Self {
-        for elem in iter {
-            self.0.push(elem);
-        }
+    pub fn proof(mut self, v: Bytes) -> Self {
+        self.proof = v;
         self
     }
-    pub fn replace(&mut self, index: usize, v: KeyValue) -> Option<KeyValue> {
-        self.0
-            .get_mut(index)
-            .map(|item| ::core::mem::replace(item, v))
-    }
 }
-impl molecule::prelude::Builder for KeyValuesBuilder {
-    type Entity = KeyValues;
-    const NAME: &'static str = "KeyValuesBuilder";
+impl molecule::prelude::Builder for DataWithProofBuilder {
+    type Entity = DataWithProof;
+    const NAME: &'static str = "DataWithProofBuilder";
     fn expected_length(&self) -> usize {
-        molecule::NUMBER_SIZE * (self.0.len() + 1)
-            + self
-                .0
-                .iter()
-                .map(|inner| inner.as_slice().len())
-                .sum::<usize>()
+        molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
+            + self.data.as_slice().len()
+            + self.proof.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
-        let item_count = self.0.len();
-        if item_count == 0 {
-            writer.write_all(&molecule::pack_number(
-                molecule::NUMBER_SIZE as molecule::Number,
-            ))?;
-        } else {
-            let (total_size, offsets) = self.0.iter().fold(
-                (
-                    molecule::NUMBER_SIZE * (item_count + 1),
-                    Vec::with_capacity(item_count),
-                ),
-                |(start, mut offsets), inner| {
-                    offsets.push(start);
-                    (start + inner.as_slice().len(), offsets)
-                },
-            );
-            writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
-            for offset in offsets.into_iter() {
-                writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
-            }
-            for inner in self.0.iter() {
-                writer.write_all(inner.as_slice())?;
-            }
+        let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
+        let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
+        offsets.push(total_size);
+        total_size += self.data.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.proof.as_slice().len();
+        writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
+        for offset in offsets.into_iter() {
+            writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
         }
+        writer.write_all(self.data.as_slice())?;
+        writer.write_all(self.proof.as_slice())?;
         Ok(())
     }
     fn build(&self) -> Self::Entity {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        KeyValues::new_unchecked(inner.into())
-    }
-}
-pub struct KeyValuesIterator(KeyValues, usize, usize);
-impl ::core::iter::Iterator for KeyValuesIterator {
-    type Item = KeyValue;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.1 >= self.2 {
-            None
-        } else {
-            let ret = self.0.get_unchecked(self.1);
-            self.1 += 1;
-            Some(ret)
-        }
-    }
-}
-impl ::core::iter::ExactSizeIterator for KeyValuesIterator {
-    fn len(&self) -> usize {
-        self.2 - self.1
-    }
-}
-impl ::core::iter::IntoIterator for KeyValues {
-    type Item = KeyValue;
-    type IntoIter = KeyValuesIterator;
-    fn into_iter(self) -> Self::IntoIter {
-        let len = self.len();
-        KeyValuesIterator(self, 0, len)
-    }
-}
-impl<'r> KeyValuesReader<'r> {
-    pub fn iter<'t>(&'t self) -> KeyValuesReaderIterator<'t, 'r> {
-        KeyValuesReaderIterator(&self, 0, self.len())
-    }
-}
-pub struct KeyValuesReaderIterator<'t, 'r>(&'t KeyValuesReader<'r>, usize, usize);
-impl<'t: 'r, 'r> ::core::iter::Iterator for KeyValuesReaderIterator<'t, 'r> {
-    type Item = KeyValueReader<'t>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.1 >= self.2 {
-            None
-        } else {
-            let ret = self.0.get_unchecked(self.1);
-            self.1 += 1;
-            Some(ret)
-        }
-    }
-}
-impl<'t: 'r, 'r> ::core::iter::ExactSizeIterator for KeyValuesReaderIterator<'t, 'r> {
-    fn len(&self) -> usize {
-        self.2 - self.1
+        DataWithProof::new_unchecked(inner.into())
     }
 }
 #[derive(Clone)]
-pub struct DataWithProof(molecule::bytes::Bytes);
-impl ::core::fmt::LowerHex for DataWithProof {
+pub struct SmtReadWrite(molecule::bytes::Bytes);
+impl ::core::fmt::LowerHex for SmtReadWrite {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2507,15 +3107,17 @@ impl ::core::fmt::LowerHex for DataWithProof {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl ::core::fmt::Debug for DataWithProof {
+impl ::core::fmt::Debug for SmtReadWrite {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl ::core::fmt::Display for DataWithProof {
+impl ::core::fmt::Display for SmtReadWrite {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "data", self.data())?;
+        write!(f, "{}: {}", "new_root", self.new_root())?;
+        write!(f, ", {}: {}", "reads", self.reads())?;
+        write!(f, ", {}: {}", "changes", self.changes())?;
         write!(f, ", {}: {}", "proof", self.proof())?;
         let extra_count = self.count_extra_fields();
         if extra_count != 0 {
@@ -2524,17 +3126,19 @@ impl ::core::fmt::Display for DataWithProof {
         write!(f, " }}")
     }
 }
-impl ::core::default::Default for DataWithProof {
+impl ::core::default::Default for SmtReadWrite {
     fn default() -> Self {
         let v = molecule::bytes::Bytes::from_static(&Self::DEFAULT_VALUE);
-        DataWithProof::new_unchecked(v)
+        SmtReadWrite::new_unchecked(v)
     }
 }
-impl DataWithProof {
-    const DEFAULT_VALUE: [u8; 20] = [
-        20, 0, 0, 0, 12, 0, 0, 0, 16, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0,
+impl SmtReadWrite {
+    const DEFAULT_VALUE: [u8; 64] = [
+        64, 0, 0, 0, 20, 0, 0, 0, 52, 0, 0, 0, 56, 0, 0, 0, 60, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0,
+        0, 0, 0, 0, 0,
     ];
-    pub const FIELD_COUNT: usize = 2;
+    pub const FIELD_COUNT: usize = 4;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2551,31 +3155,43 @@ impl DataWithProof {
     pub fn has_extra_fields(&self) -> bool {
         Self::FIELD_COUNT != self.field_count()
     }
-    pub fn data(&self) -> KeyValues {
+    pub fn new_root(&self) -> Hash {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[4..]) as usize;
         let end = molecule::unpack_number(&slice[8..]) as usize;
+        Hash::new_unchecked(self.0.slice(start..end))
+    }
+    pub fn reads(&self) -> KeyValues {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
         KeyValues::new_unchecked(self.0.slice(start..end))
     }
+    pub fn changes(&self) -> SmtChanges {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        SmtChanges::new_unchecked(self.0.slice(start..end))
+    }
     pub fn proof(&self) -> Bytes {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let start = molecule::unpack_number(&slice[16..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
+            let end = molecule::unpack_number(&slice[20..]) as usize;
             Bytes::new_unchecked(self.0.slice(start..end))
         } else {
             Bytes::new_unchecked(self.0.slice(start..))
         }
     }
-    pub fn as_reader<'r>(&'r self) -> DataWithProofReader<'r> {
-        DataWithProofReader::new_unchecked(self.as_slice())
+    pub fn as_reader<'r>(&'r self) -> SmtReadWriteReader<'r> {
+        SmtReadWriteReader::new_unchecked(self.as_slice())
     }
 }
-impl molecule::prelude::Entity for DataWithProof {
-    type Builder = DataWithProofBuilder;
-    const NAME: &'static str = "DataWithProof";
+impl molecule::prelude::Entity for SmtReadWrite {
+    type Builder = SmtReadWriteBuilder;
+    const NAME: &'static str = "SmtReadWrite";
     fn new_unchecked(data: molecule::bytes::Bytes) -> Self {
-        DataWithProof(data)
+        SmtReadWrite(data)
     }
     fn as_bytes(&self) -> molecule::bytes::Bytes {
         self.0.clone()
@@ -2584,21 +3200,25 @@ impl molecule::prelude::Entity for DataWithProof {
         &self.0[..]
     }
     fn from_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        DataWithProofReader::from_slice(slice).map(|reader| reader.to_entity())
+        SmtReadWriteReader::from_slice(slice).map(|reader| reader.to_entity())
     }
     fn from_compatible_slice(slice: &[u8]) -> molecule::error::VerificationResult<Self> {
-        DataWithProofReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
+        SmtReadWriteReader::from_compatible_slice(slice).map(|reader| reader.to_entity())
     }
     fn new_builder() -> Self::Builder {
         ::core::default::Default::default()
     }
     fn as_builder(self) -> Self::Builder {
-        Self::new_builder().data(self.data()).proof(self.proof())
+        Self::new_builder()
+            .new_root(self.new_root())
+            .reads(self.reads())
+            .changes(self.changes())
+            .proof(self.proof())
     }
 }
 #[derive(Clone, Copy)]
-pub struct DataWithProofReader<'r>(&'r [u8]);
-impl<'r> ::core::fmt::LowerHex for DataWithProofReader<'r> {
+pub struct SmtReadWriteReader<'r>(&'r [u8]);
+impl<'r> ::core::fmt::LowerHex for SmtReadWriteReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         use molecule::hex_string;
         if f.alternate() {
@@ -2607,15 +3227,17 @@ impl<'r> ::core::fmt::LowerHex for DataWithProofReader<'r> {
         write!(f, "{}", hex_string(self.as_slice()))
     }
 }
-impl<'r> ::core::fmt::Debug for DataWithProofReader<'r> {
+impl<'r> ::core::fmt::Debug for SmtReadWriteReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{}({:#x})", Self::NAME, self)
     }
 }
-impl<'r> ::core::fmt::Display for DataWithProofReader<'r> {
+impl<'r> ::core::fmt::Display for SmtReadWriteReader<'r> {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "{} {{ ", Self::NAME)?;
-        write!(f, "{}: {}", "data", self.data())?;
+        write!(f, "{}: {}", "new_root", self.new_root())?;
+        write!(f, ", {}: {}", "reads", self.reads())?;
+        write!(f, ", {}: {}", "changes", self.changes())?;
         write!(f, ", {}: {}", "proof", self.proof())?;
         let extra_count = self.count_extra_fields();
         if extra_count != 0 {
@@ -2624,8 +3246,8 @@ impl<'r> ::core::fmt::Display for DataWithProofReader<'r> {
         write!(f, " }}")
     }
 }
-impl<'r> DataWithProofReader<'r> {
-    pub const FIELD_COUNT: usize = 2;
+impl<'r> SmtReadWriteReader<'r> {
+    pub const FIELD_COUNT: usize = 4;
     pub fn total_size(&self) -> usize {
         molecule::unpack_number(self.as_slice()) as usize
     }
@@ -2642,31 +3264,43 @@ impl<'r> DataWithProofReader<'r> {
     pub fn has_extra_fields(&self) -> bool {
         Self::FIELD_COUNT != self.field_count()
     }
-    pub fn data(&self) -> KeyValuesReader<'r> {
+    pub fn new_root(&self) -> HashReader<'r> {
         let slice = self.as_slice();
         let start = molecule::unpack_number(&slice[4..]) as usize;
         let end = molecule::unpack_number(&slice[8..]) as usize;
+        HashReader::new_unchecked(&self.as_slice()[start..end])
+    }
+    pub fn reads(&self) -> KeyValuesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let end = molecule::unpack_number(&slice[12..]) as usize;
         KeyValuesReader::new_unchecked(&self.as_slice()[start..end])
     }
+    pub fn changes(&self) -> SmtChangesReader<'r> {
+        let slice = self.as_slice();
+        let start = molecule::unpack_number(&slice[12..]) as usize;
+        let end = molecule::unpack_number(&slice[16..]) as usize;
+        SmtChangesReader::new_unchecked(&self.as_slice()[start..end])
+    }
     pub fn proof(&self) -> BytesReader<'r> {
         let slice = self.as_slice();
-        let start = molecule::unpack_number(&slice[8..]) as usize;
+        let start = molecule::unpack_number(&slice[16..]) as usize;
         if self.has_extra_fields() {
-            let end = molecule::unpack_number(&slice[12..]) as usize;
+            let end = molecule::unpack_number(&slice[20..]) as usize;
             BytesReader::new_unchecked(&self.as_slice()[start..end])
         } else {
             BytesReader::new_unchecked(&self.as_slice()[start..])
         }
     }
 }
-impl<'r> molecule::prelude::Reader<'r> for DataWithProofReader<'r> {
-    type Entity = DataWithProof;
-    const NAME: &'static str = "DataWithProofReader";
+impl<'r> molecule::prelude::Reader<'r> for SmtReadWriteReader<'r> {
+    type Entity = SmtReadWrite;
+    const NAME: &'static str = "SmtReadWriteReader";
     fn to_entity(&self) -> Self::Entity {
         Self::Entity::new_unchecked(self.as_slice().to_owned().into())
     }
     fn new_unchecked(slice: &'r [u8]) -> Self {
-        DataWithProofReader(slice)
+        SmtReadWriteReader(slice)
     }
     fn as_slice(&self) -> &'r [u8] {
         self.0
@@ -2705,20 +3339,32 @@ impl<'r> molecule::prelude::Reader<'r> for DataWithProofReader<'r> {
         if offsets.windows(2).any(|i| i[0] > i[1]) {
             return ve!(Self, OffsetsNotMatch);
         }
-        KeyValuesReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
-        BytesReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        HashReader::verify(&slice[offsets[0]..offsets[1]], compatible)?;
+        KeyValuesReader::verify(&slice[offsets[1]..offsets[2]], compatible)?;
+        SmtChangesReader::verify(&slice[offsets[2]..offsets[3]], compatible)?;
+        BytesReader::verify(&slice[offsets[3]..offsets[4]], compatible)?;
         Ok(())
     }
 }
 #[derive(Debug, Default)]
-pub struct DataWithProofBuilder {
-    pub(crate) data: KeyValues,
+pub struct SmtReadWriteBuilder {
+    pub(crate) new_root: Hash,
+    pub(crate) reads: KeyValues,
+    pub(crate) changes: SmtChanges,
     pub(crate) proof: Bytes,
 }
-impl DataWithProofBuilder {
-    pub const FIELD_COUNT: usize = 2;
-    pub fn data(mut self, v: KeyValues) -> Self {
-        self.data = v;
+impl SmtReadWriteBuilder {
+    pub const FIELD_COUNT: usize = 4;
+    pub fn new_root(mut self, v: Hash) -> Self {
+        self.new_root = v;
+        self
+    }
+    pub fn reads(mut self, v: KeyValues) -> Self {
+        self.reads = v;
+        self
+    }
+    pub fn changes(mut self, v: SmtChanges) -> Self {
+        self.changes = v;
         self
     }
     pub fn proof(mut self, v: Bytes) -> Self {
@@ -2726,26 +3372,34 @@ impl DataWithProofBuilder {
         self
     }
 }
-impl molecule::prelude::Builder for DataWithProofBuilder {
-    type Entity = DataWithProof;
-    const NAME: &'static str = "DataWithProofBuilder";
+impl molecule::prelude::Builder for SmtReadWriteBuilder {
+    type Entity = SmtReadWrite;
+    const NAME: &'static str = "SmtReadWriteBuilder";
     fn expected_length(&self) -> usize {
         molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1)
-            + self.data.as_slice().len()
+            + self.new_root.as_slice().len()
+            + self.reads.as_slice().len()
+            + self.changes.as_slice().len()
             + self.proof.as_slice().len()
     }
     fn write<W: molecule::io::Write>(&self, writer: &mut W) -> molecule::io::Result<()> {
         let mut total_size = molecule::NUMBER_SIZE * (Self::FIELD_COUNT + 1);
         let mut offsets = Vec::with_capacity(Self::FIELD_COUNT);
         offsets.push(total_size);
-        total_size += self.data.as_slice().len();
+        total_size += self.new_root.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.reads.as_slice().len();
+        offsets.push(total_size);
+        total_size += self.changes.as_slice().len();
         offsets.push(total_size);
         total_size += self.proof.as_slice().len();
         writer.write_all(&molecule::pack_number(total_size as molecule::Number))?;
         for offset in offsets.into_iter() {
             writer.write_all(&molecule::pack_number(offset as molecule::Number))?;
         }
-        writer.write_all(self.data.as_slice())?;
+        writer.write_all(self.new_root.as_slice())?;
+        writer.write_all(self.reads.as_slice())?;
+        writer.write_all(self.changes.as_slice())?;
         writer.write_all(self.proof.as_slice())?;
         Ok(())
     }
@@ -2753,6 +3407,6 @@ impl molecule::prelude::Builder for DataWithProofBuilder {
         let mut inner = Vec::with_capacity(self.expected_length());
         self.write(&mut inner)
             .unwrap_or_else(|_| panic!("{} build should be ok", Self::NAME));
-        DataWithProof::new_unchecked(inner.into())
+        SmtReadWrite::new_unchecked(inner.into())
     }
 }
@@ -0,0 +1,228 @@
+//! Types for proof generation against past roots.
+
+use alloc::{collections::VecDeque, vec::Vec};
+
+use ckb_hash::blake2b_256;
+use molecule::bytes::Bytes;
+use sparse_merkle_tree::H256;
+
+use crate::{
+    error::GeneratorError,
+    types::{
+        generated::{DataWithProof, SmtUpdate, SmtUpdateReader},
+        prover::{data_with_proof_from, BytesOpt, ProofGenerator},
+        rebase::Rebase,
+    },
+};
+
+/// How many past roots should be kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrunePolicy {
+    /// Keeps all roots.
+    KeepAll,
+    /// Keeps the latest N roots, includes the current root; the current root
+    /// is always kept, even if N is zero.
+    KeepLatest(usize),
+}
+
+impl Default for PrunePolicy {
+    /// Keeps the latest 16 roots.
+    fn default() -> Self {
+        Self::KeepLatest(16)
+    }
+}
+
+/// A proof generator which keeps the changes of each commit reversely, so it
+/// could generate proofs against any kept past root.
+///
+/// Only the old values of the changed leaves are kept for each past root, the
+/// tree at a past root is restored from the current tree when it's required.
+pub struct HistoricalGenerator {
+    generator: ProofGenerator,
+    // The oldest is at the front; the current root is not included.
+    revisions: VecDeque<Revision>,
+    policy: PrunePolicy,
+}
+
+// A past root, and the old values of the leaves which are changed by the next
+// commit; restores them to revert the next root to this root.
+struct Revision {
+    root: H256,
+    old_values: Vec<(Bytes, Option<Bytes>)>,
+}
+
+impl HistoricalGenerator {
+    /// Creates a new instance with an empty tree.
+    pub fn new(policy: PrunePolicy) -> Self {
+        Self::from_generator(ProofGenerator::new(), policy)
+    }
+
+    /// Creates a new instance, the current root of the generator is the
+    /// oldest root in the history.
+    pub fn from_generator(generator: ProofGenerator, policy: PrunePolicy) -> Self {
+        Self {
+            generator,
+            revisions: VecDeque::new(),
+            policy,
+        }
+    }
+
+    /// Returns the generator of the current tree.
+    pub fn generator(&self) -> &ProofGenerator {
+        &self.generator
+    }
+
+    /// Returns current merkle root.
+    pub fn root(&self) -> &H256 {
+        self.generator.root()
+    }
+
+    /// Returns all kept roots, from the oldest to the current.
+    pub fn roots(&self) -> impl Iterator<Item = &H256> {
+        self.revisions
+            .iter()
+            .map(|revision| &revision.root)
+            .chain(core::iter::once(self.generator.root()))
+    }
+
+    /// Returns true if the root is kept.
+    pub fn contains_root(&self, root: &H256) -> bool {
+        self.roots().any(|kept| kept == root)
+    }
+
+    /// Appends a change but not commit, see [`ProofGenerator::append_change`].
+    pub fn append_change(&mut self, key: Bytes, new_value: Option<Bytes>) -> Option<Option<Bytes>> {
+        self.generator.append_change(key, new_value)
+    }
+
+    /// Commits all pending changes, and keeps the old root.
+    pub fn commit_changes(&mut self) -> Result<SmtUpdate, GeneratorError> {
+        let root = *self.generator.root();
+        let update = self.generator.commit_changes()?;
+        let old_values = old_values_of(update.as_reader());
+        self.push_revision(root, old_values);
+        Ok(update)
+    }
+
+    /// Rebases the pending changes on an update which is committed by others,
+    /// and keeps the old root, see [`ProofGenerator::rebase`].
+    pub fn rebase(&mut self, foreign: SmtUpdateReader<'_>) -> Result<Rebase, GeneratorError> {
+        let root = *self.generator.root();
+        let old_values = self.current_values_of(foreign)?;
+        let rebase = self.generator.rebase(foreign)?;
+        self.push_revision(root, old_values);
+        Ok(rebase)
+    }
+
+    /// Applies an update, and keeps the old root.
+    pub fn apply_update(&mut self, smt_update: SmtUpdateReader<'_>) -> Result<(), GeneratorError> {
+        let root = *self.generator.root();
+        let old_values = self.current_values_of(smt_update)?;
+        self.generator.apply_update(smt_update)?;
+        self.push_revision(root, old_values);
+        Ok(())
+    }
+
+    /// Gets value of a leaf at a kept root.
+    pub fn get_at(&self, root: &H256, key: &[u8]) -> Result<Option<Bytes>, GeneratorError> {
+        let index = self.checked_index_of(root)?;
+        // The first change after the root has the value at the root.
+        for revision in self.revisions.range(index..) {
+            if let Some((_, value)) = revision.old_values.iter().find(|(k, _)| k == key) {
+                return Ok(value.clone());
+            }
+        }
+        self.generator.get(key)
+    }
+
+    /// Returns the values and their proof at a kept root.
+    ///
+    /// The tree at a past root is restored from a copy of the current tree.
+    pub fn data_with_proof_at(
+        &self,
+        root: &H256,
+        keys: Vec<Bytes>,
+    ) -> Result<DataWithProof, GeneratorError> {
+        let index = self.checked_index_of(root)?;
+        if index == self.revisions.len() {
+            return self.generator.data_with_proof(keys);
+        }
+        let mut smt = self.generator.snapshot();
+        for revision in self.revisions.range(index..).rev() {
+            for (key, value) in &revision.old_values {
+                smt.update(blake2b_256(key).into(), BytesOpt(value.clone()))?;
+            }
+        }
+        data_with_proof_from(&smt, keys)
+    }
+
+    /// Changes the prune policy, and prunes the old roots immediately.
+    pub fn set_prune_policy(&mut self, policy: PrunePolicy) {
+        self.policy = policy;
+        self.prune();
+    }
+
+    // Returns the values in the current tree of the keys which are changed by
+    // the update.
+    fn current_values_of(
+        &self,
+        smt_update: SmtUpdateReader<'_>,
+    ) -> Result<Vec<(Bytes, Option<Bytes>)>, GeneratorError> {
+        smt_update
+            .changes()
+            .iter()
+            .map(|change| {
+                let key = Bytes::copy_from_slice(change.key().raw_data());
+                let value = self.generator.get(&key)?;
+                Ok((key, value))
+            })
+            .collect()
+    }
+
+    fn push_revision(&mut self, root: H256, old_values: Vec<(Bytes, Option<Bytes>)>) {
+        self.revisions.push_back(Revision { root, old_values });
+        self.prune();
+    }
+
+    fn prune(&mut self) {
+        if let PrunePolicy::KeepLatest(count) = self.policy {
+            let count = count.saturating_sub(1);
+            while self.revisions.len() > count {
+                self.revisions.pop_front();
+            }
+        }
+    }
+
+    // Returns the index of the root in the revisions, or the count of the
+    // revisions for the current root. The same root could be committed more
+    // than once, the latest one is used, they are same anyway.
+    fn index_of(&self, root: &H256) -> Option<usize> {
+        if root == self.generator.root() {
+            return Some(self.revisions.len());
+        }
+        self.revisions
+            .iter()
+            .rposition(|revision| revision.root == *root)
+    }
+
+    fn checked_index_of(&self, root: &H256) -> Result<usize, GeneratorError> {
+        self.index_of(root)
+            .ok_or(GeneratorError::UnknownRoot(*root))
+    }
+}
+
+// Returns the old values of the changes in an update.
+fn old_values_of(smt_update: SmtUpdateReader<'_>) -> Vec<(Bytes, Option<Bytes>)> {
+    smt_update
+        .changes()
+        .iter()
+        .map(|change| {
+            let key = Bytes::copy_from_slice(change.key().raw_data());
+            let value = change
+                .old_value()
+                .to_opt()
+                .map(|value| Bytes::copy_from_slice(value.raw_data()));
+            (key, value)
+        })
+        .collect()
+}
@@ -0,0 +1,259 @@
+//! JSON-friendly types, all keys, values, roots and proofs are encoded as hex
+//! strings with the `0x` prefix.
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+
+use molecule::{bytes::Bytes, prelude::*};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::{
+    generated::{self as packed, DataWithProof, KeyValue, SmtChange, SmtUpdate},
+    prover::slice_to_packed_bytes,
+};
+
+/// Bytes which are encoded as a hex string.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsonBytes(pub Bytes);
+
+/// A 32 bytes hash which is encoded as a hex string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct JsonHash(pub [u8; 32]);
+
+/// The JSON-friendly type of `KeyValue`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonKeyValue {
+    pub key: JsonBytes,
+    pub value: Option<JsonBytes>,
+}
+
+/// The JSON-friendly type of `DataWithProof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonDataWithProof {
+    pub data: Vec<JsonKeyValue>,
+    pub proof: JsonBytes,
+}
+
+/// The JSON-friendly type of `SmtChange`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonSmtChange {
+    pub key: JsonBytes,
+    pub old_value: Option<JsonBytes>,
+    pub new_value: Option<JsonBytes>,
+}
+
+/// The JSON-friendly type of `SmtUpdate`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonSmtUpdate {
+    pub new_root: JsonHash,
+    pub changes: Vec<JsonSmtChange>,
+    pub proof: JsonBytes,
+}
+
+impl Serialize for JsonBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_hex(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = decode_hex(&s).map_err(de::Error::custom)?;
+        Ok(Self(Bytes::from(bytes)))
+    }
+}
+
+impl Serialize for JsonHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_hex(&self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = decode_hex(&s).map_err(de::Error::custom)?;
+        if bytes.len() != 32 {
+            let msg = format!("the length of a hash should be 32, but got {}", bytes.len());
+            return Err(de::Error::custom(msg));
+        }
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&bytes);
+        Ok(Self(hash))
+    }
+}
+
+impl fmt::Display for JsonBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_hex(&self.0))
+    }
+}
+
+impl fmt::Display for JsonHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", encode_hex(&self.0))
+    }
+}
+
+impl From<packed::Bytes> for JsonBytes {
+    fn from(packed: packed::Bytes) -> Self {
+        Self(Bytes::copy_from_slice(packed.as_reader().raw_data()))
+    }
+}
+
+impl From<&JsonBytes> for packed::Bytes {
+    fn from(json: &JsonBytes) -> Self {
+        slice_to_packed_bytes(&json.0)
+    }
+}
+
+impl From<packed::Hash> for JsonHash {
+    fn from(packed: packed::Hash) -> Self {
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(packed.as_slice());
+        Self(hash)
+    }
+}
+
+impl From<&JsonHash> for packed::Hash {
+    fn from(json: &JsonHash) -> Self {
+        packed::Hash::new_unchecked(Bytes::copy_from_slice(&json.0))
+    }
+}
+
+fn bytes_opt_from_packed(packed: packed::BytesOpt) -> Option<JsonBytes> {
+    packed.to_opt().map(Into::into)
+}
+
+fn bytes_opt_to_packed(json: &Option<JsonBytes>) -> packed::BytesOpt {
+    packed::BytesOpt::new_builder()
+        .set(json.as_ref().map(Into::into))
+        .build()
+}
+
+impl From<KeyValue> for JsonKeyValue {
+    fn from(packed: KeyValue) -> Self {
+        Self {
+            key: packed.key().into(),
+            value: bytes_opt_from_packed(packed.value()),
+        }
+    }
+}
+
+impl From<&JsonKeyValue> for KeyValue {
+    fn from(json: &JsonKeyValue) -> Self {
+        KeyValue::new_builder()
+            .key((&json.key).into())
+            .value(bytes_opt_to_packed(&json.value))
+            .build()
+    }
+}
+
+impl From<JsonKeyValue> for KeyValue {
+    fn from(json: JsonKeyValue) -> Self {
+        (&json).into()
+    }
+}
+
+impl From<DataWithProof> for JsonDataWithProof {
+    fn from(packed: DataWithProof) -> Self {
+        Self {
+            data: packed.data().into_iter().map(Into::into).collect(),
+            proof: packed.proof().into(),
+        }
+    }
+}
+
+impl From<&JsonDataWithProof> for DataWithProof {
+    fn from(json: &JsonDataWithProof) -> Self {
+        let data = packed::KeyValues::new_builder()
+            .set(json.data.iter().map(Into::into).collect())
+            .build();
+        DataWithProof::new_builder()
+            .data(data)
+            .proof((&json.proof).into())
+            .build()
+    }
+}
+
+impl From<JsonDataWithProof> for DataWithProof {
+    fn from(json: JsonDataWithProof) -> Self {
+        (&json).into()
+    }
+}
+
+impl From<SmtChange> for JsonSmtChange {
+    fn from(packed: SmtChange) -> Self {
+        Self {
+            key: packed.key().into(),
+            old_value: bytes_opt_from_packed(packed.old_value()),
+            new_value: bytes_opt_from_packed(packed.new_value()),
+        }
+    }
+}
+
+impl From<&JsonSmtChange> for SmtChange {
+    fn from(json: &JsonSmtChange) -> Self {
+        SmtChange::new_builder()
+            .key((&json.key).into())
+            .old_value(bytes_opt_to_packed(&json.old_value))
+            .new_value(bytes_opt_to_packed(&json.new_value))
+            .build()
+    }
+}
+
+impl From<JsonSmtChange> for SmtChange {
+    fn from(json: JsonSmtChange) -> Self {
+        (&json).into()
+    }
+}
+
+impl From<SmtUpdate> for JsonSmtUpdate {
+    fn from(packed: SmtUpdate) -> Self {
+        Self {
+            new_root: packed.new_root().into(),
+            changes: packed.changes().into_iter().map(Into::into).collect(),
+            proof: packed.proof().into(),
+        }
+    }
+}
+
+impl From<&JsonSmtUpdate> for SmtUpdate {
+    fn from(json: &JsonSmtUpdate) -> Self {
+        let changes = packed::SmtChanges::new_builder()
+            .set(json.changes.iter().map(Into::into).collect())
+            .build();
+        SmtUpdate::new_builder()
+            .new_root((&json.new_root).into())
+            .changes(changes)
+            .proof((&json.proof).into())
+            .build()
+    }
+}
+
+impl From<JsonSmtUpdate> for SmtUpdate {
+    fn from(json: JsonSmtUpdate) -> Self {
+        (&json).into()
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    format!("0x{}", faster_hex::hex_string(bytes))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let hex = s
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("the hex string should start with \"0x\", but got {s:?}"))?;
+    if hex.is_empty() {
+        return Ok(Vec::new());
+    }
+    if hex.len() % 2 != 0 {
+        return Err(format!("the length of the hex string is odd: {s:?}"));
+    }
+    let mut bytes = alloc::vec![0u8; hex.len() / 2];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes)
+        .map_err(|err| format!("invalid hex string {s:?}: {err}"))?;
+    Ok(bytes)
+}
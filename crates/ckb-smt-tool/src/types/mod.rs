@@ -2,18 +2,48 @@
 
 #![allow(missing_docs)]
 
+#[cfg(feature = "with-prover")]
+pub(crate) mod budget;
+#[cfg(feature = "with-prover")]
+pub(crate) mod bundle;
 #[allow(warnings)]
 #[allow(clippy::all)]
 pub(crate) mod generated;
 
+#[cfg(feature = "with-prover")]
+pub(crate) mod estimate;
+#[cfg(feature = "with-prover")]
+pub(crate) mod history;
+#[cfg(feature = "serde")]
+pub mod json;
 #[cfg(feature = "with-prover")]
 pub(crate) mod prover;
+#[cfg(feature = "with-prover")]
+pub(crate) mod rebase;
+#[cfg(feature = "with-prover")]
+pub(crate) mod snapshot;
+#[cfg(feature = "with-prover")]
+pub(crate) mod squash;
 pub(crate) mod verifier;
 
+#[cfg(feature = "with-prover")]
+pub use budget::CommitBudget;
+#[cfg(feature = "with-prover")]
+pub use bundle::{merge_data_with_proofs, split_data_with_proof};
+#[cfg(feature = "with-prover")]
+pub use estimate::Estimate;
+#[cfg(feature = "with-prover")]
+pub use history::{HistoricalGenerator, PrunePolicy};
 #[cfg(feature = "with-prover")]
 pub use molecule::bytes::Bytes;
 #[cfg(feature = "with-prover")]
 pub use prover::{ProofGenerator, Smt};
+#[cfg(feature = "with-prover")]
+pub use rebase::{Conflict, Rebase};
 pub use sparse_merkle_tree::H256;
 
-pub use generated::{DataWithProof, DataWithProofReader, SmtUpdate, SmtUpdateReader};
+pub use generated::{
+    DataWithProof, DataWithProofReader, KeyValue, KeyValueReader, SmtChange, SmtChangeReader,
+    SmtReadWrite, SmtReadWriteReader, SmtUpdate, SmtUpdateChain, SmtUpdateChainReader,
+    SmtUpdateReader,
+};
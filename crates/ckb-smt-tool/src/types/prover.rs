@@ -1,7 +1,12 @@
 //! Types for proof generation.
 
-use alloc::vec::Vec;
-use std::collections::HashMap;
+use alloc::{collections::BTreeMap, vec::Vec};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
 
 use ckb_hash::blake2b_256;
 use molecule::{bytes::Bytes, prelude::*, Number, NUMBER_SIZE};
@@ -12,7 +17,15 @@ use sparse_merkle_tree::{
 
 use crate::{
     error::GeneratorError,
-    types::generated::{self as packed, DataWithProof, SmtUpdate, SmtUpdateReader},
+    types::{
+        budget::CommitBudget,
+        estimate::Estimate,
+        generated::{
+            self as packed, DataWithProof, SmtReadWrite, SmtUpdate, SmtUpdateChain, SmtUpdateReader,
+        },
+        rebase::{Conflict, Rebase},
+        snapshot, squash,
+    },
 };
 
 pub type Smt = SparseMerkleTree<Blake2bHasher, BytesOpt, DefaultStore<BytesOpt>>;
@@ -21,12 +34,20 @@ pub type Smt = SparseMerkleTree<Blake2bHasher, BytesOpt, DefaultStore<BytesOpt>>
 #[derive(Default)]
 pub struct ProofGenerator {
     smt: Smt,
-    changes: HashMap<Bytes, Option<Bytes>>,
+    changes: HashMap<Bytes, PendingChange>,
+    // The original keys of all leaves, and their hashes.
+    keys: BTreeMap<Bytes, H256>,
+}
+
+// A pending change, with the old value which is expected in the tree.
+struct PendingChange {
+    expected: Option<Bytes>,
+    new_value: Option<Bytes>,
 }
 
 /// Wrap `Option<Bytes>` to implement the trait `Value`.
 #[derive(Default, Clone)]
-pub struct BytesOpt(Option<Bytes>);
+pub struct BytesOpt(pub(crate) Option<Bytes>);
 
 impl Value for BytesOpt {
     fn to_h256(&self) -> H256 {
@@ -62,9 +83,7 @@ impl ProofGenerator {
 
     /// Gets value of a leaf return zero value if leaf not exists.
     pub fn get(&self, key: &[u8]) -> Result<Option<Bytes>, GeneratorError> {
-        let key_h256 = blake2b_256(key).into();
-        let value = self.smt.get(&key_h256)?.0;
-        Ok(value)
+        get_from(&self.smt, key)
     }
 
     /// Update a leaf, return new merkle root set to zero value to delete a key.
@@ -74,8 +93,40 @@ impl ProofGenerator {
         value_opt: Option<Bytes>,
     ) -> Result<&H256, GeneratorError> {
         let key_h256 = blake2b_256(key).into();
+        let exists = value_opt.is_some();
         let value = BytesOpt(value_opt);
-        self.smt.update(key_h256, value).map_err(Into::into)
+        self.smt.update(key_h256, value)?;
+        sync_key(
+            &mut self.keys,
+            Bytes::copy_from_slice(key),
+            key_h256,
+            exists,
+        );
+        Ok(self.smt.root())
+    }
+
+    /// Updates multiple leaves at once, returns new merkle root.
+    ///
+    /// The leaves are sorted and updated together by
+    /// [`SparseMerkleTree::update_all`], so each branch is computed once.
+    ///
+    /// If a key occurs more than once, the last value is used.
+    pub fn update_all<I>(&mut self, leaves: I) -> Result<&H256, GeneratorError>
+    where
+        I: IntoIterator<Item = (Bytes, Option<Bytes>)>,
+    {
+        let mut keys = Vec::new();
+        let mut smt_leaves = Vec::new();
+        for (key, value_opt) in leaves {
+            let key_h256 = blake2b_256(&key).into();
+            keys.push((key, key_h256, value_opt.is_some()));
+            smt_leaves.push((key_h256, BytesOpt(value_opt)));
+        }
+        self.smt.update_all(smt_leaves)?;
+        for (key, key_h256, exists) in keys {
+            sync_key(&mut self.keys, key, key_h256, exists);
+        }
+        Ok(self.smt.root())
     }
 
     /// Returns current merkle root.
@@ -83,6 +134,33 @@ impl ProofGenerator {
         self.smt.root()
     }
 
+    /// Returns the count of leaves.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if there is no leaf.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the original keys of all leaves, sorted by the keys.
+    pub fn keys(&self) -> impl Iterator<Item = &Bytes> {
+        self.keys.keys()
+    }
+
+    /// Returns the original keys and the values of all leaves, sorted by the
+    /// keys.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bytes, &Bytes)> {
+        let leaves = self.smt.store().leaves_map();
+        self.keys.iter().filter_map(move |(key, key_h256)| {
+            leaves
+                .get(key_h256)
+                .and_then(|value| value.0.as_ref())
+                .map(|value| (key, value))
+        })
+    }
+
     /// Generates merkle proof.
     pub fn merkle_proof(&self, keys: Vec<Bytes>) -> Result<MerkleProof, GeneratorError> {
         let keys_h256: Vec<_> = keys
@@ -95,46 +173,66 @@ impl ProofGenerator {
 
     /// Returns the values and their proof.
     pub fn data_with_proof(&self, keys: Vec<Bytes>) -> Result<DataWithProof, GeneratorError> {
-        let mut keys_h256 = Vec::new();
-        let mut kvs_builder = packed::KeyValues::new_builder();
-        for k in keys {
-            let key_h256: H256 = blake2b_256(&k).into();
-            let key = slice_to_packed_bytes(&k);
-            let value = self.smt.get(&key_h256)?.to_packed();
-            let kv = packed::KeyValue::new_builder()
-                .key(key)
-                .value(value)
-                .build();
-            kvs_builder = kvs_builder.push(kv);
-            keys_h256.push(key_h256);
-        }
-        let data = kvs_builder.build();
-        let proof = {
-            let proof = self
-                .smt
-                .merkle_proof(keys_h256.clone())?
-                .compile(keys_h256)?;
-            slice_to_packed_bytes(&proof.0)
-        };
-        let data_with_proof = packed::DataWithProof::new_builder()
-            .data(data)
-            .proof(proof)
-            .build();
-        Ok(data_with_proof)
+        data_with_proof_from(&self.smt, keys)
     }
 
     /// Appends a change but not commit; returns the previous pending change of
     /// the same key if there is any.
+    ///
+    /// The current value in the tree is recorded as the expected old value,
+    /// unless there is already a pending change of the same key.
     pub fn append_change(&mut self, key: Bytes, new_value: Option<Bytes>) -> Option<Option<Bytes>> {
-        self.changes.insert(key, new_value)
+        let expected = match self.changes.get(&key) {
+            Some(change) => change.expected.clone(),
+            None => {
+                let key_h256: H256 = blake2b_256(&key).into();
+                let leaves = self.smt.store().leaves_map();
+                leaves.get(&key_h256).and_then(|value| value.0.clone())
+            }
+        };
+        self.append_change_if(key, expected, new_value)
+    }
+
+    /// Appends a change which expects the old value, as a compare-and-swap;
+    /// returns the previous pending change of the same key if there is any.
+    ///
+    /// The expected old value is checked by
+    /// [`compare_and_commit`](Self::compare_and_commit) and
+    /// [`rebase`](Self::rebase).
+    pub fn append_change_if(
+        &mut self,
+        key: Bytes,
+        expected: Option<Bytes>,
+        new_value: Option<Bytes>,
+    ) -> Option<Option<Bytes>> {
+        let change = PendingChange {
+            expected,
+            new_value,
+        };
+        self.changes
+            .insert(key, change)
+            .map(|change| change.new_value)
+    }
+
+    /// Returns true if there is any pending change.
+    pub fn has_pending_changes(&self) -> bool {
+        !self.changes.is_empty()
     }
 
     /// Commits all pending changes, and returns an update which contains the proof of changes.
     pub fn commit_changes(&mut self) -> Result<SmtUpdate, GeneratorError> {
         let mut keys_h256 = Vec::new();
         let mut smt_changes_builder = packed::SmtChanges::new_builder();
-        for (key, value_opt) in self.changes.drain() {
+        for (
+            key,
+            PendingChange {
+                new_value: value_opt,
+                ..
+            },
+        ) in self.changes.drain()
+        {
             let key_h256 = blake2b_256(&key).into();
+            let exists = value_opt.is_some();
             let new_value = BytesOpt(value_opt);
             let old_value = self.smt.get(&key_h256)?;
 
@@ -149,6 +247,7 @@ impl ProofGenerator {
             smt_changes_builder = smt_changes_builder.push(smt_change);
 
             self.smt.update(key_h256, new_value)?;
+            sync_key(&mut self.keys, key, key_h256, exists);
             keys_h256.push(key_h256);
         }
         let new_root = {
@@ -172,6 +271,232 @@ impl ProofGenerator {
         Ok(update)
     }
 
+    /// Commits each batch of changes as an update, and returns all updates as
+    /// a chain; the pending changes are committed with the first batch.
+    pub fn commit_chain<I, B>(&mut self, batches: I) -> Result<SmtUpdateChain, GeneratorError>
+    where
+        I: IntoIterator<Item = B>,
+        B: IntoIterator<Item = (Bytes, Option<Bytes>)>,
+    {
+        let mut builder = SmtUpdateChain::new_builder();
+        for batch in batches {
+            for (key, new_value) in batch {
+                self.append_change(key, new_value);
+            }
+            builder = builder.push(self.commit_changes()?);
+        }
+        Ok(builder.build())
+    }
+
+    /// Commits all pending changes as several updates, each of them is within
+    /// the budget; the updates chain from the current root to the final root.
+    ///
+    /// The changes are sorted by the hashes of their keys, so the keys in one
+    /// update share as many branches as possible. If a single change exceeds
+    /// the budget, nothing is committed and the pending changes are kept.
+    pub fn commit_changes_within(
+        &mut self,
+        budget: CommitBudget,
+    ) -> Result<Vec<SmtUpdate>, GeneratorError> {
+        let changes = self.sorted_changes();
+        let mut smt = self.snapshot();
+        let mut updates = Vec::new();
+        let mut rest = &changes[..];
+        while !rest.is_empty() {
+            let (len, update) = largest_batch_within(&smt, rest, budget)?;
+            for (key_h256, _, new_value) in &rest[..len] {
+                smt.update(*key_h256, new_value.clone())?;
+            }
+            updates.push(update);
+            rest = &rest[len..];
+        }
+
+        self.smt = smt;
+        for (key_h256, key, new_value) in changes {
+            sync_key(&mut self.keys, key, key_h256, new_value.0.is_some());
+        }
+        self.changes.clear();
+        Ok(updates)
+    }
+
+    /// Commits all pending changes, and proves the values of the read keys at
+    /// the old root in the same proof.
+    ///
+    /// The reads are sorted by the hashes of their keys. The duplicated read
+    /// keys are only kept once, and the read keys which are also changed are
+    /// dropped, since their old values are proven by the changes.
+    pub fn commit_read_write(
+        &mut self,
+        read_keys: Vec<Bytes>,
+    ) -> Result<SmtReadWrite, GeneratorError> {
+        let changes = self.sorted_changes();
+        let mut reads: BTreeMap<H256, Bytes> = BTreeMap::new();
+        for key in read_keys {
+            if !self.changes.contains_key(&key) {
+                reads.entry(blake2b_256(&key).into()).or_insert(key);
+            }
+        }
+
+        let keys_h256: Vec<_> = reads
+            .keys()
+            .copied()
+            .chain(changes.iter().map(|(key_h256, _, _)| *key_h256))
+            .collect();
+        let proof = self
+            .smt
+            .merkle_proof(keys_h256.clone())?
+            .compile(keys_h256)?;
+        let mut kvs_builder = packed::KeyValues::new_builder();
+        for (key_h256, key) in &reads {
+            let kv = packed::KeyValue::new_builder()
+                .key(slice_to_packed_bytes(key))
+                .value(self.smt.get(key_h256)?.to_packed())
+                .build();
+            kvs_builder = kvs_builder.push(kv);
+        }
+        let smt_changes = pack_changes(&self.smt, &changes)?;
+
+        for (key_h256, key, new_value) in changes {
+            let exists = new_value.0.is_some();
+            self.smt.update(key_h256, new_value)?;
+            sync_key(&mut self.keys, key, key_h256, exists);
+        }
+        self.changes.clear();
+        let new_root = Bytes::copy_from_slice(self.smt.root().as_slice());
+        let read_write = SmtReadWrite::new_builder()
+            .new_root(packed::Hash::new_unchecked(new_root))
+            .reads(kvs_builder.build())
+            .changes(smt_changes)
+            .proof(slice_to_packed_bytes(&proof.0))
+            .build();
+        Ok(read_write)
+    }
+
+    /// Estimates the `DataWithProof` of the keys, without returning it.
+    pub fn estimate_data_with_proof(&self, keys: Vec<Bytes>) -> Result<Estimate, GeneratorError> {
+        let data_with_proof = self.data_with_proof(keys)?;
+        Ok(Estimate::of_data_with_proof(data_with_proof.as_reader()))
+    }
+
+    /// Estimates the `SmtUpdate` which would be returned by
+    /// [`commit_changes`](Self::commit_changes), without committing anything.
+    pub fn estimate_pending_changes(&self) -> Result<Estimate, GeneratorError> {
+        let update = build_update(&self.smt, &self.sorted_changes())?;
+        Ok(Estimate::of_smt_update(update.as_reader()))
+    }
+
+    // Returns the pending changes with the hashes of their keys, sorted by the
+    // hashes.
+    fn sorted_changes(&self) -> Vec<(H256, Bytes, BytesOpt)> {
+        let mut changes: Vec<_> = self
+            .changes
+            .iter()
+            .map(|(key, change)| {
+                let key_h256: H256 = blake2b_256(key).into();
+                (key_h256, key.clone(), BytesOpt(change.new_value.clone()))
+            })
+            .collect();
+        changes.sort_unstable_by_key(|(key_h256, _, _)| *key_h256);
+        changes
+    }
+
+    /// Returns the pending changes whose expected old values are not the
+    /// values in the tree, sorted by the keys.
+    pub fn conflicts(&self) -> Result<Vec<Conflict>, GeneratorError> {
+        let mut conflicts = Vec::new();
+        for (key, change) in &self.changes {
+            let actual = get_from(&self.smt, key)?;
+            if actual != change.expected {
+                conflicts.push(Conflict {
+                    key: key.clone(),
+                    expected: change.expected.clone(),
+                    actual,
+                    new_value: change.new_value.clone(),
+                });
+            }
+        }
+        conflicts.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        Ok(conflicts)
+    }
+
+    /// Returns the update of all pending changes against the current root,
+    /// without committing them; fails if there is any conflict.
+    ///
+    /// Once the update is accepted on chain, commit the changes by
+    /// [`commit_changes`](Self::commit_changes); otherwise, if the tree is
+    /// changed by others, call [`rebase`](Self::rebase).
+    pub fn prepare_changes(&self) -> Result<SmtUpdate, GeneratorError> {
+        self.check_conflicts()?;
+        build_update(&self.smt, &self.sorted_changes())
+    }
+
+    /// Commits all pending changes only if the expected old values of all
+    /// of them are the values in the tree; nothing is committed otherwise.
+    pub fn compare_and_commit(&mut self) -> Result<SmtUpdate, GeneratorError> {
+        self.check_conflicts()?;
+        self.commit_changes()
+    }
+
+    /// Applies an update which is committed by others against the current
+    /// root, then drops and reports the pending changes which conflict with
+    /// it, and prepares an update of the rest pending changes against the new
+    /// root.
+    ///
+    /// The pending changes which are already applied by the update, for
+    /// example, when the update is prepared by self, are dropped without
+    /// being reported.
+    pub fn rebase(&mut self, foreign: SmtUpdateReader<'_>) -> Result<Rebase, GeneratorError> {
+        foreign
+            .verify_smt(self.smt.root())
+            .map_err(GeneratorError::InvalidForeignUpdate)?;
+        self.apply_update(foreign)?;
+
+        let mut conflicts = Vec::new();
+        for conflict in self.conflicts()? {
+            self.changes.remove(&conflict.key);
+            // Otherwise, the change is already applied by the update.
+            if conflict.actual != conflict.new_value {
+                conflicts.push(conflict);
+            }
+        }
+        let update = if self.has_pending_changes() {
+            Some(build_update(&self.smt, &self.sorted_changes())?)
+        } else {
+            None
+        };
+        Ok(Rebase { conflicts, update })
+    }
+
+    /// Squashes a sequence of updates which starts from the current root into
+    /// one update, which has the net change of each key; the tree is not
+    /// changed.
+    ///
+    /// For each key, the old value is the value in the tree, and the new value
+    /// is from the last update which changes it; the keys whose values are not
+    /// changed in the end are removed. The proof is compiled against the
+    /// current root.
+    ///
+    /// Fails with [`GeneratorError::NoNetChange`] if no key is changed in the
+    /// end, for example, when the updates revert each other.
+    pub fn squash_updates(
+        &self,
+        updates: &[SmtUpdateReader<'_>],
+    ) -> Result<SmtUpdate, GeneratorError> {
+        let changes = squash::net_changes(&self.smt, updates)?;
+        build_update(&self.smt, &changes)
+    }
+
+    // Fails with the keys of all conflicts if there is any.
+    fn check_conflicts(&self) -> Result<(), GeneratorError> {
+        let conflicts = self.conflicts()?;
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            let keys = conflicts.into_iter().map(|conflict| conflict.key).collect();
+            Err(GeneratorError::Conflicts(keys))
+        }
+    }
+
     /// Applies an update.
     pub fn apply_update(&mut self, smt_update: SmtUpdateReader<'_>) -> Result<(), GeneratorError> {
         for data in smt_update.changes().iter() {
@@ -185,9 +510,181 @@ impl ProofGenerator {
         }
         Ok(())
     }
+
+    /// Saves all leaves and the root of the tree into a file, see
+    /// [`write_snapshot`](Self::write_snapshot).
+    pub fn save_snapshot<P: AsRef<Path>>(&self, path: P) -> Result<(), GeneratorError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_snapshot(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Loads a tree from a file, see [`read_snapshot`](Self::read_snapshot).
+    pub fn load_snapshot<P: AsRef<Path>>(path: P) -> Result<Self, GeneratorError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::read_snapshot(&mut reader)
+    }
+
+    /// Writes all leaves and the root of the tree in a compact binary format.
+    ///
+    /// Pending changes are not included, commit them before saving if they
+    /// should be kept.
+    pub fn write_snapshot<W: Write>(&self, writer: &mut W) -> Result<(), GeneratorError> {
+        let leaves: Vec<_> = self
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        snapshot::write(writer, self.smt.root(), &leaves)
+    }
+
+    /// Reads a tree which is written by [`write_snapshot`](Self::write_snapshot),
+    /// then rebuilds the tree and checks its root.
+    pub fn read_snapshot<R: Read>(reader: &mut R) -> Result<Self, GeneratorError> {
+        let (expected, leaves) = snapshot::read(reader)?;
+        let mut generator = Self::new();
+        let leaves = leaves.into_iter().map(|(key, value)| (key, Some(value)));
+        let actual = *generator.update_all(leaves)?;
+        if actual != expected {
+            return Err(GeneratorError::MismatchedSnapshotRoot { expected, actual });
+        }
+        Ok(generator)
+    }
+
+    /// Copies the current tree.
+    pub(crate) fn snapshot(&self) -> Smt {
+        Smt::new(*self.smt.root(), self.smt.store().clone())
+    }
+}
+
+// Keeps the original key of a leaf, or removes it if the leaf is removed.
+fn sync_key(keys: &mut BTreeMap<Bytes, H256>, key: Bytes, key_h256: H256, exists: bool) {
+    if exists {
+        keys.insert(key, key_h256);
+    } else {
+        keys.remove(&key);
+    }
+}
+
+// Finds the longest prefix of the changes whose update is within the budget,
+// by doubling the length at first, then searching between the last two
+// lengths; returns the length and the update.
+fn largest_batch_within(
+    smt: &Smt,
+    changes: &[(H256, Bytes, BytesOpt)],
+    budget: CommitBudget,
+) -> Result<(usize, SmtUpdate), GeneratorError> {
+    let try_len = |len: usize| -> Result<Option<SmtUpdate>, GeneratorError> {
+        let update = build_update(smt, &changes[..len])?;
+        Ok(budget.allows(update.as_reader()).then_some(update))
+    };
+
+    let mut fit = 1;
+    let mut best =
+        try_len(fit)?.ok_or_else(|| GeneratorError::ExceededBudget(changes[0].1.clone()))?;
+    let mut unfit = None;
+    while fit < changes.len() {
+        let len = (fit * 2).min(changes.len());
+        match try_len(len)? {
+            Some(update) => (fit, best) = (len, update),
+            None => {
+                unfit = Some(len);
+                break;
+            }
+        }
+    }
+    if let Some(mut unfit) = unfit {
+        while unfit - fit > 1 {
+            let len = fit + (unfit - fit) / 2;
+            match try_len(len)? {
+                Some(update) => (fit, best) = (len, update),
+                None => unfit = len,
+            }
+        }
+    }
+    Ok((fit, best))
+}
+
+// Builds the update of the changes without applying them; the siblings of
+// the changed keys are not changed, so the proof against the current tree is
+// also valid for the new root.
+fn build_update(
+    smt: &Smt,
+    changes: &[(H256, Bytes, BytesOpt)],
+) -> Result<SmtUpdate, GeneratorError> {
+    let keys_h256: Vec<_> = changes.iter().map(|(key_h256, _, _)| *key_h256).collect();
+    let new_leaves = changes
+        .iter()
+        .map(|(key_h256, _, new_value)| (*key_h256, new_value.to_h256()))
+        .collect();
+    let proof = smt.merkle_proof(keys_h256.clone())?.compile(keys_h256)?;
+    let new_root = proof.compute_root::<Blake2bHasher>(new_leaves)?;
+    let update = SmtUpdate::new_builder()
+        .new_root(packed::Hash::new_unchecked(Bytes::copy_from_slice(
+            new_root.as_slice(),
+        )))
+        .changes(pack_changes(smt, changes)?)
+        .proof(slice_to_packed_bytes(&proof.0))
+        .build();
+    Ok(update)
+}
+
+// Packs the changes with their old values in the tree.
+fn pack_changes(
+    smt: &Smt,
+    changes: &[(H256, Bytes, BytesOpt)],
+) -> Result<packed::SmtChanges, GeneratorError> {
+    let mut smt_changes_builder = packed::SmtChanges::new_builder();
+    for (key_h256, key, new_value) in changes {
+        let old_value = smt.get(key_h256)?;
+        let smt_change = packed::SmtChange::new_builder()
+            .key(slice_to_packed_bytes(key))
+            .old_value(old_value.to_packed())
+            .new_value(new_value.to_packed())
+            .build();
+        smt_changes_builder = smt_changes_builder.push(smt_change);
+    }
+    Ok(smt_changes_builder.build())
+}
+
+/// Gets value of a leaf in the tree.
+pub(crate) fn get_from(smt: &Smt, key: &[u8]) -> Result<Option<Bytes>, GeneratorError> {
+    let key_h256 = blake2b_256(key).into();
+    let value = smt.get(&key_h256)?.0;
+    Ok(value)
+}
+
+/// Returns the values and their proof in the tree.
+pub(crate) fn data_with_proof_from(
+    smt: &Smt,
+    keys: Vec<Bytes>,
+) -> Result<DataWithProof, GeneratorError> {
+    let mut keys_h256 = Vec::new();
+    let mut kvs_builder = packed::KeyValues::new_builder();
+    for k in keys {
+        let key_h256: H256 = blake2b_256(&k).into();
+        let key = slice_to_packed_bytes(&k);
+        let value = smt.get(&key_h256)?.to_packed();
+        let kv = packed::KeyValue::new_builder()
+            .key(key)
+            .value(value)
+            .build();
+        kvs_builder = kvs_builder.push(kv);
+        keys_h256.push(key_h256);
+    }
+    let data = kvs_builder.build();
+    let proof = {
+        let proof = smt.merkle_proof(keys_h256.clone())?.compile(keys_h256)?;
+        slice_to_packed_bytes(&proof.0)
+    };
+    let data_with_proof = packed::DataWithProof::new_builder()
+        .data(data)
+        .proof(proof)
+        .build();
+    Ok(data_with_proof)
 }
 
-fn slice_to_packed_bytes(slice: &[u8]) -> packed::Bytes {
+pub(crate) fn slice_to_packed_bytes(slice: &[u8]) -> packed::Bytes {
     let len = slice.len();
     let mut vec: Vec<u8> = Vec::with_capacity(NUMBER_SIZE + len);
     vec.extend_from_slice(&(len as Number).to_le_bytes()[..]);
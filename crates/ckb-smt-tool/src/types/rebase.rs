@@ -0,0 +1,36 @@
+//! Results of the optimistic concurrency control.
+//!
+//! Each pending change records the value which is expected in the tree; when
+//! the tree is changed by others, the pending changes whose expected values
+//! are not in the tree any more are conflicts.
+
+use alloc::vec::Vec;
+
+use molecule::bytes::Bytes;
+
+use crate::types::generated::SmtUpdate;
+
+/// A pending change whose expected old value is not the value in the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The original key.
+    pub key: Bytes,
+    /// The old value which the change expected.
+    pub expected: Option<Bytes>,
+    /// The value in the tree.
+    pub actual: Option<Bytes>,
+    /// The new value of the change.
+    pub new_value: Option<Bytes>,
+}
+
+/// The result of
+/// [`ProofGenerator::rebase`](crate::types::ProofGenerator::rebase).
+#[derive(Debug, Clone)]
+pub struct Rebase {
+    /// The conflicts which are dropped from the pending changes, sorted by
+    /// the keys.
+    pub conflicts: Vec<Conflict>,
+    /// The update of the rest pending changes against the new root, it's not
+    /// committed; `None` if there is no pending change any more.
+    pub update: Option<SmtUpdate>,
+}
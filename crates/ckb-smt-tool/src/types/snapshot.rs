@@ -0,0 +1,117 @@
+//! The binary format of snapshots of the tree.
+//!
+//! A snapshot only contains the leaves, all branches are rebuilt when it is
+//! loaded, and the rebuilt root should be same as the saved root.
+//!
+//! All integers are little-endian:
+//!
+//! | Field       | Size                                                  |
+//! |-------------|-------------------------------------------------------|
+//! | magic       | 4 bytes, `SMTS`                                       |
+//! | version     | 1 byte, `1`                                           |
+//! | root        | 32 bytes                                              |
+//! | leaves      | 8 bytes, the count of leaves                          |
+//! | each leaf   | 4 bytes length, the key, 4 bytes length, the value    |
+//!
+//! Leaves are sorted by their original keys, and there is no duplicated key;
+//! the keys are hashed when the tree is rebuilt.
+
+use alloc::{format, vec::Vec};
+use std::io::{Read, Write};
+
+use molecule::bytes::Bytes;
+use sparse_merkle_tree::H256;
+
+use crate::error::GeneratorError;
+
+const MAGIC: [u8; 4] = *b"SMTS";
+const VERSION: u8 = 1;
+
+// Limits the capacity which is allocated before any leaf is read, since the
+// count of leaves is not trusted.
+const MAX_PREALLOCATED_LEAVES: usize = 1024;
+
+pub(crate) fn write<W: Write>(
+    writer: &mut W,
+    root: &H256,
+    leaves: &[(Bytes, Bytes)],
+) -> Result<(), GeneratorError> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    writer.write_all(root.as_slice())?;
+    writer.write_all(&(leaves.len() as u64).to_le_bytes())?;
+    for (key, value) in leaves {
+        write_bytes(writer, key)?;
+        write_bytes(writer, value)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn read<R: Read>(reader: &mut R) -> Result<(H256, Vec<(Bytes, Bytes)>), GeneratorError> {
+    let magic: [u8; 4] = read_array(reader)?;
+    if magic != MAGIC {
+        return Err(GeneratorError::InvalidSnapshot(format!(
+            "unknown magic {magic:?}"
+        )));
+    }
+    let [version] = read_array(reader)?;
+    if version != VERSION {
+        return Err(GeneratorError::InvalidSnapshot(format!(
+            "unsupported version {version}"
+        )));
+    }
+    let root: H256 = read_array::<_, 32>(reader)?.into();
+    let count = u64::from_le_bytes(read_array(reader)?);
+
+    let capacity = usize::try_from(count)
+        .unwrap_or(usize::MAX)
+        .min(MAX_PREALLOCATED_LEAVES);
+    let mut leaves: Vec<(Bytes, Bytes)> = Vec::with_capacity(capacity);
+    for _ in 0..count {
+        let key = read_bytes(reader)?;
+        if let Some((last_key, _)) = leaves.last() {
+            if *last_key >= key {
+                return Err(GeneratorError::InvalidSnapshot(
+                    "leaves are not sorted by their keys".into(),
+                ));
+            }
+        }
+        let value = read_bytes(reader)?;
+        leaves.push((key, value));
+    }
+
+    if reader.read(&mut [0u8])? != 0 {
+        return Err(GeneratorError::InvalidSnapshot("trailing data".into()));
+    }
+    Ok((root, leaves))
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), GeneratorError> {
+    let len = u32::try_from(bytes.len())
+        .map_err(|_| GeneratorError::InvalidSnapshot(format!("too long: {}", bytes.len())))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(bytes)?;
+    Ok(())
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> Result<Bytes, GeneratorError> {
+    let len = u64::from(u32::from_le_bytes(read_array(reader)?));
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(GeneratorError::InvalidSnapshot("unexpected end".into()));
+    }
+    Ok(Bytes::from(bytes))
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], GeneratorError> {
+    let mut buf = [0u8; N];
+    reader.read_exact(&mut buf).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::UnexpectedEof {
+            GeneratorError::InvalidSnapshot("unexpected end".into())
+        } else {
+            err.into()
+        }
+    })?;
+    Ok(buf)
+}
@@ -0,0 +1,59 @@
+//! Squash several `SmtUpdate` into one.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use ckb_hash::blake2b_256;
+use molecule::bytes::Bytes;
+use sparse_merkle_tree::H256;
+
+use crate::{
+    error::GeneratorError,
+    types::{
+        generated::SmtUpdateReader,
+        prover::{BytesOpt, Smt},
+        verifier::hash_to_h256,
+    },
+};
+
+/// Verifies a sequence of updates which starts from the root of the tree,
+/// and returns the net change of each key, sorted by the hashes of the keys.
+///
+/// The new value of a key is from the last update which changes it; the keys
+/// whose new values are the values in the tree are removed. Fails with
+/// [`GeneratorError::NoNetChange`] if no key is changed in the end.
+pub(crate) fn net_changes(
+    smt: &Smt,
+    updates: &[SmtUpdateReader<'_>],
+) -> Result<Vec<(H256, Bytes, BytesOpt)>, GeneratorError> {
+    let mut root = *smt.root();
+    let mut changes: BTreeMap<H256, (Bytes, Option<Bytes>)> = BTreeMap::new();
+    for (index, update) in updates.iter().enumerate() {
+        update
+            .verify_smt(&root)
+            .map_err(|error| GeneratorError::InvalidSmtUpdate { index, error })?;
+        root = hash_to_h256(update.new_root().raw_data());
+
+        for change in update.changes().iter() {
+            let key = change.key().raw_data();
+            let new_value = change
+                .new_value()
+                .to_opt()
+                .map(|value| Bytes::copy_from_slice(value.raw_data()));
+            changes.insert(
+                blake2b_256(key).into(),
+                (Bytes::copy_from_slice(key), new_value),
+            );
+        }
+    }
+
+    let mut net_changes = Vec::with_capacity(changes.len());
+    for (key_h256, (key, new_value)) in changes {
+        if smt.get(&key_h256)?.0 != new_value {
+            net_changes.push((key_h256, key, BytesOpt(new_value)));
+        }
+    }
+    if net_changes.is_empty() {
+        return Err(GeneratorError::NoNetChange);
+    }
+    Ok(net_changes)
+}
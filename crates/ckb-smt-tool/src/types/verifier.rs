@@ -11,7 +11,7 @@ use crate::{
     error::{UpdateError, VerifyError},
     types::generated::{
         BytesOptReader, DataWithProofReader, KeyValuesReader, SmtChangeReader, SmtChangesReader,
-        SmtUpdateReader,
+        SmtReadWriteReader, SmtUpdateChainReader, SmtUpdateReader,
     },
 };
 
@@ -55,6 +55,86 @@ impl SmtUpdateReader<'_> {
     }
 }
 
+impl SmtUpdateChainReader<'_> {
+    /// Verifies all updates in order, from the old SMT root; the old root of
+    /// each update is the new root of the previous one.
+    pub fn verify_smt(&self, old_root: &H256) -> Result<(), UpdateError> {
+        if self.is_empty() {
+            return Err(UpdateError::EmptyChain);
+        }
+        let mut root = *old_root;
+        for smt_update in self.iter() {
+            smt_update.verify_smt(&root)?;
+            root = hash_to_h256(smt_update.new_root().raw_data());
+        }
+        Ok(())
+    }
+
+    /// Returns the new root of the last update.
+    pub fn new_root(&self) -> Option<H256> {
+        self.iter()
+            .last()
+            .map(|smt_update| hash_to_h256(smt_update.new_root().raw_data()))
+    }
+}
+
+impl SmtReadWriteReader<'_> {
+    /// Verifies self with the old SMT root: the reads and the old values of
+    /// the changes are checked against the old root, then the reads and the
+    /// new values of the changes are checked against the new root.
+    ///
+    /// A key should not be both read and changed.
+    pub fn verify_smt(&self, old_root: &H256) -> Result<(), UpdateError> {
+        let proof_data = self.proof().raw_data().to_vec();
+        let proof = CompiledMerkleProof(proof_data);
+        let reads = self.reads().as_leaves();
+        let leaves_changes = self.changes().leaves_changes();
+
+        let mut keys: Vec<_> = reads
+            .iter()
+            .map(|(key, _)| *key)
+            .chain(leaves_changes.iter().map(|lc| lc.key))
+            .collect();
+        keys.sort_unstable();
+        if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(UpdateError::DuplicatedKey);
+        }
+
+        let old_leaves = reads
+            .iter()
+            .copied()
+            .chain(leaves_changes.iter().map(|lc| (lc.key, lc.old_value)))
+            .collect();
+        let expected_old_root = if let Ok(root) = proof.compute_root::<Blake2bHasher>(old_leaves) {
+            root
+        } else {
+            return Err(UpdateError::ComputeOldRoot);
+        };
+        if expected_old_root != *old_root {
+            return Err(UpdateError::MismatchedOldRoot);
+        }
+        let new_leaves = reads
+            .into_iter()
+            .chain(leaves_changes.into_iter().map(|lc| (lc.key, lc.new_value)))
+            .collect();
+        let expected_new_root = if let Ok(root) = proof.compute_root::<Blake2bHasher>(new_leaves) {
+            root
+        } else {
+            return Err(UpdateError::ComputeNewRoot);
+        };
+        if expected_new_root.as_slice() != self.new_root().raw_data() {
+            return Err(UpdateError::MismatchedNewRoot);
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn hash_to_h256(hash: &[u8]) -> H256 {
+    let mut root = [0u8; 32];
+    root.copy_from_slice(hash);
+    root.into()
+}
+
 impl SmtChangeReader<'_> {
     fn key_to_h256(&self) -> H256 {
         blake2b_256(self.key().raw_data()).into()
@@ -0,0 +1,35 @@
+//! Unique ID for cells which store an SMT root.
+//!
+//! The unique ID is calculated like the Type ID: hash the first input of the
+//! transaction which creates the cell, and the index of the created cell in
+//! outputs.
+
+use ckb_hash::{new_blake2b, BLAKE2B_LEN};
+#[cfg(feature = "onchain")]
+use ckb_std::{ckb_constants::Source, ckb_types::prelude::*, error::SysError, high_level as hl};
+
+/// The length of a unique ID.
+pub const UNIQUE_ID_LEN: usize = BLAKE2B_LEN;
+
+/// Calculates an unique ID with the first input and the index of the output.
+///
+/// The first input should be the serialized `CellInput`.
+pub fn calculate_unique_id(first_input: &[u8], output_index: usize) -> [u8; UNIQUE_ID_LEN] {
+    let mut blake2b = new_blake2b();
+    blake2b.update(first_input);
+    blake2b.update(&(output_index as u64).to_le_bytes());
+    let mut ret = [0; UNIQUE_ID_LEN];
+    blake2b.finalize(&mut ret);
+    ret
+}
+
+/// Loads the first input of the current transaction, then calculates an
+/// unique ID for the output at `output_index`.
+///
+/// This method could only be used on chain.
+#[cfg(feature = "onchain")]
+pub fn load_then_calculate_unique_id(output_index: usize) -> Result<[u8; UNIQUE_ID_LEN], SysError> {
+    let input = hl::load_input(0, Source::Input)?;
+    let ret = calculate_unique_id(input.as_slice(), output_index);
+    Ok(ret)
+}
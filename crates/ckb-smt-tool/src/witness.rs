@@ -0,0 +1,119 @@
+//! Place SMT witnesses into `WitnessArgs`, and extract them from transactions.
+
+use core::fmt;
+
+use ckb_types::{core::TransactionView, packed, prelude::*};
+
+use crate::{
+    error::WitnessError,
+    types::{DataWithProof, DataWithProofReader, SmtUpdate, SmtUpdateReader},
+};
+
+/// The fields of `WitnessArgs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessField {
+    Lock,
+    InputType,
+    OutputType,
+}
+
+impl fmt::Display for WitnessField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Lock => "lock",
+            Self::InputType => "input_type",
+            Self::OutputType => "output_type",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl WitnessField {
+    /// Sets the field of a `WitnessArgs`, other fields are kept.
+    pub fn set(self, witness_args: packed::WitnessArgs, data: &[u8]) -> packed::WitnessArgs {
+        let bytes_opt = packed::BytesOpt::new_builder()
+            .set(Some(data.pack()))
+            .build();
+        let builder = witness_args.as_builder();
+        match self {
+            Self::Lock => builder.lock(bytes_opt),
+            Self::InputType => builder.input_type(bytes_opt),
+            Self::OutputType => builder.output_type(bytes_opt),
+        }
+        .build()
+    }
+
+    /// Gets the field of a `WitnessArgs`.
+    pub fn get(self, witness_args: &packed::WitnessArgs) -> Option<packed::Bytes> {
+        match self {
+            Self::Lock => witness_args.lock(),
+            Self::InputType => witness_args.input_type(),
+            Self::OutputType => witness_args.output_type(),
+        }
+        .to_opt()
+    }
+}
+
+/// Creates a `WitnessArgs` with an `SmtUpdate` in the chosen field.
+pub fn smt_update_witness_args(field: WitnessField, smt_update: &SmtUpdate) -> packed::WitnessArgs {
+    field.set(Default::default(), smt_update.as_slice())
+}
+
+/// Creates a `WitnessArgs` with a `DataWithProof` in the chosen field.
+pub fn data_with_proof_witness_args(
+    field: WitnessField,
+    data_with_proof: &DataWithProof,
+) -> packed::WitnessArgs {
+    field.set(Default::default(), data_with_proof.as_slice())
+}
+
+/// Extracts the `SmtUpdate` from the chosen field of the witness at `index`.
+pub fn extract_smt_update(
+    tx: &TransactionView,
+    index: usize,
+    field: WitnessField,
+) -> Result<SmtUpdate, WitnessError> {
+    let bytes = extract_field(tx, index, field)?;
+    SmtUpdateReader::verify(bytes.as_reader().raw_data(), false).map_err(|source| {
+        WitnessError::InvalidField {
+            index,
+            field,
+            source,
+        }
+    })?;
+    Ok(SmtUpdate::new_unchecked(bytes.raw_data()))
+}
+
+/// Extracts the `DataWithProof` from the chosen field of the witness at
+/// `index`.
+pub fn extract_data_with_proof(
+    tx: &TransactionView,
+    index: usize,
+    field: WitnessField,
+) -> Result<DataWithProof, WitnessError> {
+    let bytes = extract_field(tx, index, field)?;
+    DataWithProofReader::verify(bytes.as_reader().raw_data(), false).map_err(|source| {
+        WitnessError::InvalidField {
+            index,
+            field,
+            source,
+        }
+    })?;
+    Ok(DataWithProof::new_unchecked(bytes.raw_data()))
+}
+
+fn extract_field(
+    tx: &TransactionView,
+    index: usize,
+    field: WitnessField,
+) -> Result<packed::Bytes, WitnessError> {
+    let witness = tx
+        .witnesses()
+        .get(index)
+        .ok_or(WitnessError::NotExisted(index))?;
+    let witness_args = packed::WitnessArgs::from_slice(&witness.raw_data())
+        .map_err(|source| WitnessError::InvalidWitnessArgs { index, source })?;
+    field
+        .get(&witness_args)
+        .ok_or(WitnessError::EmptyField { index, field })
+}
@@ -0,0 +1,320 @@
+//! Cycles consumed by the on-chain verification.
+//!
+//! The benchmark is ignored by default, since it takes a while; run it by:
+//!
+//! ```sh
+//! make benchmark
+//! ```
+//!
+//! Environment variables:
+//!
+//! - `BENCHMARK_RECORD`: if set, saves the results as the new baseline instead
+//!   of comparing with the baseline.
+//! - `BENCHMARK_THRESHOLD`: the max allowed regression in percentage, default
+//!   is 5.
+//! - `BENCHMARK_BASELINE`: the path of the baseline file, default is
+//!   `benchmark/baseline.json` in the `tests` crate.
+//!
+//! The cycles which are estimated by `ckb_smt_tool::types::Estimate` are
+//! printed beside the measured cycles, to calibrate the constants of the
+//! estimation; a few small cases are checked by `estimate_within_error_bound`,
+//! which is not ignored.
+
+use std::{collections::BTreeMap, env, fs, path::PathBuf};
+
+use ckb_smt_tool::{
+    tx_builder::build_update_tx,
+    types::{Estimate, ProofGenerator},
+    witness::{data_with_proof_witness_args, WitnessField},
+};
+use ckb_testtool::{
+    builtin::ALWAYS_SUCCESS,
+    ckb_types::{
+        bytes::Bytes,
+        core::{Cycle, DepType, TransactionBuilder},
+        packed::*,
+        prelude::*,
+    },
+    context::Context,
+};
+
+use crate::{prelude::*, utilities, Loader};
+
+const KEYS_COUNTS: &[usize] = &[1, 10, 100, 1_000];
+const TREE_SIZES: &[usize] = &[100, 1_000, 10_000];
+const DEFAULT_THRESHOLD: f64 = 5.0;
+// The max allowed error of the estimated cycles, in percentage.
+const ESTIMATE_ERROR_BOUND: f64 = 10.0;
+
+type Results = BTreeMap<String, Cycle>;
+type Estimates = BTreeMap<String, Cycle>;
+
+fn key(index: usize) -> Bytes {
+    Bytes::from(format!("key-{index}"))
+}
+
+fn value(index: usize, version: u8) -> Bytes {
+    let mut value = [version; 32];
+    value[..8].copy_from_slice(&(index as u64).to_le_bytes());
+    Bytes::copy_from_slice(&value)
+}
+
+fn build_generator(tree_size: usize) -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for i in 0..tree_size {
+        generator
+            .update(&key(i), Some(value(i, 0)))
+            .expect("smt update");
+    }
+    generator
+}
+
+fn case_name(operation: &str, keys_count: usize, tree_size: usize) -> String {
+    format!("{operation}/keys={keys_count}/tree={tree_size}")
+}
+
+// Updates the first `keys_count` keys; if there are not enough keys in the
+// tree, the rest are inserted.
+//
+// Returns the measured cycles and the estimated cycles.
+fn measure_smt_update(keys_count: usize, tree_size: usize) -> (Cycle, Cycle) {
+    let mut context = Context::default();
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+    let type_script = context
+        .build_script(&type_out_point, Bytes::from([0u8; 32].to_vec()))
+        .expect("type script");
+
+    let mut generator = build_generator(tree_size);
+    let old_root = Bytes::copy_from_slice(generator.root().as_slice());
+    for i in 0..keys_count {
+        generator.append_change(key(i), Some(value(i, 1)));
+    }
+    let estimate = generator
+        .estimate_pending_changes()
+        .expect("estimate pending changes");
+
+    let cell = CellOutput::new_builder()
+        .capacity(1000u64.pack())
+        .lock(lock_script)
+        .type_(Some(type_script).pack())
+        .build();
+    let out_point = context.create_cell(cell.clone(), old_root.clone());
+    let tx = build_update_tx(&mut generator, cell, &old_root, out_point).expect("build tx");
+    let tx = context.complete_tx(tx);
+    let cycles = context
+        .should_be_passed_without_limit(&tx)
+        .expect("verify tx");
+    (cycles, estimate.cycles)
+}
+
+// Proves the first `keys_count` keys; if there are not enough keys in the
+// tree, the rest are proved as absent.
+//
+// Returns the measured cycles and the estimated cycles.
+fn measure_data_with_proof(keys_count: usize, generator: &ProofGenerator) -> (Cycle, Cycle) {
+    let mut context = Context::default();
+    let success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+    let success_lock_script = context
+        .build_script(&success_out_point, Default::default())
+        .expect("success lock script");
+    let kvstore_type_script = context
+        .build_script(&success_out_point, Bytes::from([0u8; 32].to_vec()))
+        .expect("kvstore type script");
+    let demo_lock_script = {
+        let contract_bin: Bytes = Loader::default().load_binary("demo-check-data");
+        let out_point = context.deploy_cell(contract_bin);
+        let args = kvstore_type_script.calc_script_hash().raw_data();
+        context
+            .build_script(&out_point, args)
+            .expect("demo lock script")
+    };
+
+    let keys = (0..keys_count).map(key).collect();
+    let data_with_proof = generator
+        .data_with_proof(keys)
+        .expect("generate data with proof");
+    let estimate = Estimate::of_data_with_proof(data_with_proof.as_reader());
+
+    let kvstore_cell_dep = {
+        let out_point = context.create_cell(
+            CellOutput::new_builder()
+                .capacity(1000u64.pack())
+                .lock(success_lock_script)
+                .type_(Some(kvstore_type_script).pack())
+                .build(),
+            Bytes::copy_from_slice(generator.root().as_slice()),
+        );
+        CellDep::new_builder()
+            .out_point(out_point)
+            .dep_type(DepType::Code.into())
+            .build()
+    };
+    let input = {
+        let out_point = context.create_cell(
+            CellOutput::new_builder()
+                .capacity(1000u64.pack())
+                .lock(demo_lock_script.clone())
+                .build(),
+            Bytes::new(),
+        );
+        CellInput::new_builder().previous_output(out_point).build()
+    };
+    let output = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(demo_lock_script)
+        .build();
+    let witness = data_with_proof_witness_args(WitnessField::Lock, &data_with_proof).as_bytes();
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(kvstore_cell_dep)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+    let cycles = context
+        .should_be_passed_without_limit(&tx)
+        .expect("verify tx");
+    (cycles, estimate.cycles)
+}
+
+fn baseline_path() -> PathBuf {
+    env::var("BENCHMARK_BASELINE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            path.push("benchmark");
+            path.push("baseline.json");
+            path
+        })
+}
+
+fn threshold() -> f64 {
+    env::var("BENCHMARK_THRESHOLD")
+        .map(|s| s.parse().expect("parse BENCHMARK_THRESHOLD"))
+        .unwrap_or(DEFAULT_THRESHOLD)
+}
+
+fn load_baseline() -> Results {
+    let path = baseline_path();
+    assert!(
+        path.exists(),
+        "no baseline found at {path:?}, record it by setting BENCHMARK_RECORD"
+    );
+    let json = fs::read_to_string(&path).expect("read baseline");
+    serde_json::from_str(&json).expect("parse baseline")
+}
+
+fn save_baseline(results: &Results) {
+    let path = baseline_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("create baseline dir");
+    }
+    let json = serde_json::to_string_pretty(results).expect("json");
+    fs::write(&path, json).expect("write baseline");
+    println!("Baseline written to {path:?}");
+}
+
+// Prints the results as a markdown table, and returns the cases which exceed
+// the threshold.
+fn compare(results: &Results, baseline: Option<&Results>, threshold: f64) -> Vec<String> {
+    let mut regressions = Vec::new();
+    println!();
+    println!("| Case | Cycles | Baseline | Change |");
+    println!("|------|-------:|---------:|-------:|");
+    for (name, cycles) in results {
+        let base = baseline.and_then(|baseline| baseline.get(name));
+        let (base_str, change_str) = if let Some(base) = base {
+            let change = (*cycles as f64 - *base as f64) * 100.0 / *base as f64;
+            if change > threshold {
+                regressions.push(format!("{name}: {base} -> {cycles} ({change:+.2}%)"));
+            }
+            (base.to_string(), format!("{change:+.2}%"))
+        } else {
+            ("-".to_owned(), "-".to_owned())
+        };
+        println!("| {name} | {cycles} | {base_str} | {change_str} |");
+    }
+    println!();
+    regressions
+}
+
+// Prints the estimated cycles beside the measured cycles as a markdown table.
+fn compare_estimates(results: &Results, estimates: &Estimates) {
+    println!();
+    println!("| Case | Cycles | Estimate | Ratio |");
+    println!("|------|-------:|---------:|------:|");
+    for (name, cycles) in results {
+        let estimate = estimates[name];
+        let ratio = estimate as f64 / *cycles as f64;
+        println!("| {name} | {cycles} | {estimate} | {ratio:.2} |");
+    }
+    println!();
+}
+
+fn assert_estimate(name: &str, cycles: Cycle, estimate: Cycle) {
+    let error = (estimate as f64 - cycles as f64) * 100.0 / cycles as f64;
+    assert!(
+        error.abs() <= ESTIMATE_ERROR_BOUND,
+        "{name}: the estimate {estimate} is {error:+.2}% off the measured cycles {cycles}"
+    );
+}
+
+#[test]
+fn estimate_within_error_bound() {
+    utilities::setup();
+
+    let tree_size = 1_000;
+    let generator = build_generator(tree_size);
+    for keys_count in [1, 10] {
+        let (cycles, estimate) = measure_smt_update(keys_count, tree_size);
+        let name = case_name("smt_update", keys_count, tree_size);
+        assert_estimate(&name, cycles, estimate);
+        let (cycles, estimate) = measure_data_with_proof(keys_count, &generator);
+        let name = case_name("data_with_proof", keys_count, tree_size);
+        assert_estimate(&name, cycles, estimate);
+    }
+}
+
+#[test]
+#[ignore]
+fn cycles() {
+    utilities::setup();
+
+    let mut results = Results::new();
+    let mut estimates = Estimates::new();
+    for &tree_size in TREE_SIZES {
+        let generator = build_generator(tree_size);
+        for &keys_count in KEYS_COUNTS {
+            let name = case_name("smt_update", keys_count, tree_size);
+            let (cycles, estimate) = measure_smt_update(keys_count, tree_size);
+            results.insert(name.clone(), cycles);
+            estimates.insert(name, estimate);
+            let name = case_name("data_with_proof", keys_count, tree_size);
+            let (cycles, estimate) = measure_data_with_proof(keys_count, &generator);
+            results.insert(name.clone(), cycles);
+            estimates.insert(name, estimate);
+        }
+    }
+    compare_estimates(&results, &estimates);
+
+    if env::var("BENCHMARK_RECORD").is_ok() {
+        let _ = compare(&results, None, 0.0);
+        save_baseline(&results);
+        return;
+    }
+    let threshold = threshold();
+    let baseline = load_baseline();
+    let regressions = compare(&results, Some(&baseline), threshold);
+    assert!(
+        regressions.is_empty(),
+        "cycles regressed more than {threshold}%:\n{}",
+        regressions.join("\n")
+    );
+}
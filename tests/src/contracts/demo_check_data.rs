@@ -1,25 +1,123 @@
-use ckb_smt_tool::types::ProofGenerator;
+use ckb_smt_tool::{
+    error::VerifyError,
+    types::{DataWithProof, ProofGenerator},
+    witness::{data_with_proof_witness_args, WitnessField},
+};
 use ckb_testtool::{
     builtin::ALWAYS_SUCCESS,
+    ckb_hash::blake2b_256,
     ckb_types::{
         bytes::Bytes,
-        core::{DepType, TransactionBuilder},
+        core::{
+            DepType, EpochNumberWithFraction, HeaderBuilder, HeaderView, TransactionBuilder,
+            TransactionView,
+        },
         packed::*,
         prelude::*,
     },
     context::Context,
 };
+use demo_check_data::error::InternalError;
 
-use crate::{prelude::*, utilities, Loader};
+use crate::{
+    contracts::fixtures::{self, build_generator, build_input, build_output, create_kvstore_cell},
+    prelude::*,
+    utilities, Loader,
+};
 
-fn build_generator() -> ProofGenerator {
-    let mut generator = ProofGenerator::new();
-    for i in 0..20u8 {
-        let k = [i; 4];
-        let v = Some(Bytes::copy_from_slice(&[i; 10]));
-        generator.update(&k, v).expect("smt update");
-    }
+fn build_data_with_proof(generator: &ProofGenerator) -> DataWithProof {
+    let keys = (0..5u8)
+        .map(|x| Bytes::copy_from_slice(&[x * 5; 4]))
+        .collect();
     generator
+        .data_with_proof(keys)
+        .expect("generate data with proof")
+}
+
+// Returns the lock script which always success, the type script of the
+// KV-store cell and the lock script of the demo contract.
+//
+// The type script of the KV-store cell always success, so the KV-store cell
+// could be put into inputs.
+fn build_scripts(context: &mut Context, extra_lock_args: &[u8]) -> (Script, Script, Script) {
+    let success_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+    let success_lock_script = context
+        .build_script(&success_out_point, Default::default())
+        .expect("success lock script");
+    let kvstore_type_script = context
+        .build_script(&success_out_point, Bytes::from([0u8; 32].to_vec()))
+        .expect("kvstore type script");
+    let demo_lock_script = {
+        let contract_bin: Bytes = Loader::default().load_binary("demo-check-data");
+        let out_point = context.deploy_cell(contract_bin);
+        let mut args = kvstore_type_script.calc_script_hash().raw_data().to_vec();
+        args.extend_from_slice(extra_lock_args);
+        context
+            .build_script(&out_point, Bytes::from(args))
+            .expect("demo lock script")
+    };
+    (success_lock_script, kvstore_type_script, demo_lock_script)
+}
+
+// A header at the given block number, with 1000 blocks in each epoch.
+fn build_header(number: u64) -> HeaderView {
+    let epoch = EpochNumberWithFraction::new(number / 1000, number % 1000, 1000);
+    HeaderBuilder::default()
+        .number(number.pack())
+        .epoch(epoch.pack())
+        .build()
+}
+
+// Root source: a cell dep; max age: unlimited; witness: lock; then the
+// condition.
+fn build_condition_lock_args(key: &[u8], predicate: u8, params: &[u8]) -> Vec<u8> {
+    let mut args = vec![0x00];
+    args.extend_from_slice(&0u64.to_le_bytes());
+    args.push(0x00);
+    args.extend_from_slice(&blake2b_256(key));
+    args.push(predicate);
+    args.extend_from_slice(params);
+    args
+}
+
+fn build_cell_dep(out_point: OutPoint) -> CellDep {
+    CellDep::new_builder()
+        .out_point(out_point)
+        .dep_type(DepType::Code.into())
+        .build()
+}
+
+fn build_witness(data_with_proof: &DataWithProof) -> Bytes {
+    data_with_proof_witness_args(WitnessField::Lock, data_with_proof).as_bytes()
+}
+
+// Builds a transaction which unlocks one cell of the demo lock, and the
+// KV-store cells are put into cell deps.
+fn build_tx(
+    context: &mut Context,
+    cell_deps: Vec<CellDep>,
+    demo_lock: &Script,
+    witnesses: Vec<Bytes>,
+) -> TransactionView {
+    let input = build_input(context, demo_lock);
+    let output = build_output(demo_lock);
+    let outputs = vec![(output, Bytes::new())];
+    fixtures::build_tx(context, cell_deps, vec![input], outputs, witnesses)
+}
+
+// Builds a transaction with the proof in the lock field, and a KV-store cell
+// in cell deps which stores the root.
+fn build_simple_tx(
+    context: &mut Context,
+    extra_lock_args: &[u8],
+    root: &[u8],
+    data_with_proof: &DataWithProof,
+) -> TransactionView {
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(context, extra_lock_args);
+    let out_point = create_kvstore_cell(context, &success_lock, &kvstore_type, root);
+    let cell_dep = build_cell_dep(out_point);
+    let witness = build_witness(data_with_proof);
+    build_tx(context, vec![cell_dep], &demo_lock, vec![witness])
 }
 
 #[test]
@@ -100,16 +198,282 @@ fn success() {
         .lock(demo_lock_script.clone())
         .build();
 
+    let witness = data_with_proof_witness_args(WitnessField::Lock, &data_with_proof).as_bytes();
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(smt_cell_dep)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_multiple_cell_deps() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: one or more cell deps.
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[0x01]);
+    let cell_deps = (0..3)
+        .map(|_| {
+            let out_point =
+                create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+            build_cell_dep(out_point)
+        })
+        .collect::<Vec<_>>();
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_deps(cell_deps)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_root_in_input() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: an input.
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[0x02]);
+    let kvstore_input = {
+        let out_point =
+            create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+        CellInput::new_builder().previous_output(out_point).build()
+    };
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .input(kvstore_input)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(Bytes::new().pack())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_fresh_root() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: 10 blocks.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&10u64.to_le_bytes());
+        args
+    };
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+
+    let root_header = build_header(100);
+    let latest_header = build_header(110);
+    context.insert_header(root_header.clone());
+    context.insert_header(latest_header.clone());
+
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    context.link_cell_with_block(out_point.clone(), root_header.hash(), 0);
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .header_dep(root_header.hash())
+        .header_dep(latest_header.hash())
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_proof_in_input_type() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: unlimited; witness: input type.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&0u64.to_le_bytes());
+        args.push(0x01);
+        args
+    };
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
     let witness = {
-        let lock_args = BytesOpt::new_builder()
-            .set(Some(data_with_proof.as_slice().pack()))
-            .build();
-        let witness_args = WitnessArgs::new_builder().lock(lock_args).build();
-        witness_args.as_bytes()
+        let witness_args = data_with_proof_witness_args(WitnessField::InputType, &data_with_proof);
+        // The lock field is left for other usages, such as a signature.
+        WitnessField::Lock.set(witness_args, &[0u8; 65]).as_bytes()
     };
 
     let tx = TransactionBuilder::default()
-        .cell_dep(smt_cell_dep)
+        .cell_dep(cell_dep)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_proof_in_extra_witness() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: unlimited; witness: extra.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&0u64.to_le_bytes());
+        args.push(0x03);
+        args
+    };
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let inputs = (0..2)
+        .map(|_| build_input(&mut context, &demo_lock))
+        .collect::<Vec<_>>();
+    let output = build_output(&demo_lock);
+    let witnesses = {
+        // The witnesses for inputs are left for other usages.
+        let mut witnesses = vec![Bytes::new(); inputs.len()];
+        witnesses.push(data_with_proof.as_bytes());
+        witnesses
+    };
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .inputs(inputs)
+        .output(output)
+        .output_data(Default::default())
+        .witnesses(witnesses.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_condition_equals() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // The value of the key `[5u8; 4]` is `[5u8; 10]`.
+    let extra_lock_args = build_condition_lock_args(&[5u8; 4], 0x02, &blake2b_256([5u8; 10]));
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_condition_in_range() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let key = Bytes::from(b"balance".to_vec());
+    let mut generator = build_generator();
+    generator
+        .update(&key, Some(Bytes::from(100u64.to_le_bytes().to_vec())))
+        .expect("smt update");
+    let root = generator.root().to_owned();
+    let data_with_proof = generator
+        .data_with_proof(vec![key.clone()])
+        .expect("generate data with proof");
+
+    let extra_lock_args = {
+        let mut params = 50u64.to_le_bytes().to_vec();
+        params.extend_from_slice(&200u64.to_le_bytes());
+        build_condition_lock_args(&key, 0x03, &params)
+    };
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
         .input(input)
         .output(output)
         .output_data(Default::default())
@@ -119,3 +483,630 @@ fn success() {
 
     let _ = context.should_be_passed_without_limit(&tx);
 }
+
+#[test]
+fn success_with_condition_absent() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    let key = Bytes::from(b"blacklisted".to_vec());
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = generator
+        .data_with_proof(vec![key.clone()])
+        .expect("generate data with proof");
+
+    let extra_lock_args = build_condition_lock_args(&key, 0x01, &[]);
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+// Following exit codes of the contract are unreachable, so they are not tested:
+// - `ItemMissing`, `LengthNotEnough` and `Unknown`: the contract only loads
+//   whole items, and a missing header is reported as `HeaderDepNotFound`.
+#[test]
+fn no_witness() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[]);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let tx = build_tx(&mut context, vec![cell_dep], &demo_lock, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::IndexOutOfBound as i8,
+    );
+}
+
+#[test]
+fn witness_with_invalid_encoding() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[]);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    let witness = WitnessField::Lock
+        .set(WitnessArgs::default(), &[0xff; 16])
+        .as_bytes();
+    let tx = build_tx(&mut context, vec![cell_dep], &demo_lock, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::Encoding as i8,
+    );
+}
+
+#[test]
+fn invalid_args_length() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let tx = build_simple_tx(
+        &mut context,
+        &[0x00, 0x00],
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::InvalidArgsLength as i8,
+    );
+}
+
+#[test]
+fn cell_dep_more_than_one() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[]);
+    let cell_deps = (0..2)
+        .map(|_| {
+            let out_point =
+                create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+            build_cell_dep(out_point)
+        })
+        .collect::<Vec<_>>();
+    let witness = build_witness(&data_with_proof);
+    let tx = build_tx(&mut context, cell_deps, &demo_lock, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::CellDepMoreThanOne as i8,
+    );
+}
+
+#[test]
+fn cell_dep_not_found() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let data_with_proof = build_data_with_proof(&generator);
+    let (_, _, demo_lock) = build_scripts(&mut context, &[]);
+    let witness = build_witness(&data_with_proof);
+    let tx = build_tx(&mut context, vec![], &demo_lock, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::CellDepNotFound as i8,
+    );
+}
+
+#[test]
+fn cell_dep_invalid_cell_data() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let tx = build_simple_tx(&mut context, &[], &root.as_slice()[..31], &data_with_proof);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::CellDepInvalidCellData as i8,
+    );
+}
+
+#[test]
+fn witness_is_not_existed() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[]);
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    let cell_dep = build_cell_dep(out_point);
+    // The proof is put into a wrong field.
+    let witness = data_with_proof_witness_args(WitnessField::InputType, &data_with_proof);
+    let tx = build_tx(
+        &mut context,
+        vec![cell_dep],
+        &demo_lock,
+        vec![witness.as_bytes()],
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::WitnessIsNotExisted as i8,
+    );
+}
+
+#[test]
+fn invalid_root_source() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let tx = build_simple_tx(&mut context, &[0x03], root.as_slice(), &data_with_proof);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::InvalidRootSource as i8,
+    );
+}
+
+#[test]
+fn cell_dep_root_is_mismatch() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: one or more cell deps.
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[0x01]);
+    let cell_deps = [root.as_slice(), &[1u8; 32]]
+        .into_iter()
+        .map(|root| {
+            let out_point = create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root);
+            build_cell_dep(out_point)
+        })
+        .collect::<Vec<_>>();
+    let witness = build_witness(&data_with_proof);
+    let tx = build_tx(&mut context, cell_deps, &demo_lock, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::CellDepRootIsMismatch as i8,
+    );
+}
+
+#[test]
+fn input_more_than_one() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: an input.
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[0x02]);
+    let kvstore_inputs = (0..2)
+        .map(|_| {
+            let out_point =
+                create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+            CellInput::new_builder().previous_output(out_point).build()
+        })
+        .collect::<Vec<_>>();
+    let inputs = {
+        let mut inputs = kvstore_inputs;
+        inputs.push(build_input(&mut context, &demo_lock));
+        inputs
+    };
+    let outputs = vec![(build_output(&demo_lock), Bytes::new())];
+    let witnesses = vec![Bytes::new(), Bytes::new(), build_witness(&data_with_proof)];
+    let tx = fixtures::build_tx(&mut context, vec![], inputs, outputs, witnesses);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(2),
+        InternalError::InputMoreThanOne as i8,
+    );
+}
+
+#[test]
+fn input_not_found() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: an input; but the KV-store cell is in cell deps.
+    let tx = build_simple_tx(&mut context, &[0x02], root.as_slice(), &data_with_proof);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::InputNotFound as i8,
+    );
+}
+
+#[test]
+fn input_invalid_cell_data() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: an input.
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &[0x02]);
+    let kvstore_input = {
+        let out_point = create_kvstore_cell(
+            &mut context,
+            &success_lock,
+            &kvstore_type,
+            &root.as_slice()[..31],
+        );
+        CellInput::new_builder().previous_output(out_point).build()
+    };
+    let input = build_input(&mut context, &demo_lock);
+    let outputs = vec![(build_output(&demo_lock), Bytes::new())];
+    let witnesses = vec![Bytes::new(), build_witness(&data_with_proof)];
+    let tx = fixtures::build_tx(
+        &mut context,
+        vec![],
+        vec![kvstore_input, input],
+        outputs,
+        witnesses,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(1),
+        InternalError::InputInvalidCellData as i8,
+    );
+}
+
+#[test]
+fn header_dep_not_found() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: 10 blocks; but no header deps.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&10u64.to_le_bytes());
+        args
+    };
+    let tx = build_simple_tx(
+        &mut context,
+        &extra_lock_args,
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::HeaderDepNotFound as i8,
+    );
+}
+
+#[test]
+fn root_is_too_old() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: 10 blocks.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&10u64.to_le_bytes());
+        args
+    };
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(&mut context, &extra_lock_args);
+
+    let root_header = build_header(100);
+    let latest_header = build_header(111);
+    context.insert_header(root_header.clone());
+    context.insert_header(latest_header.clone());
+
+    let out_point =
+        create_kvstore_cell(&mut context, &success_lock, &kvstore_type, root.as_slice());
+    context.link_cell_with_block(out_point.clone(), root_header.hash(), 0);
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(&mut context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&data_with_proof);
+
+    let tx = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .header_dep(root_header.hash())
+        .header_dep(latest_header.hash())
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::RootIsTooOld as i8,
+    );
+}
+
+#[test]
+fn invalid_witness_location() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+
+    // Root source: a cell dep; max age: unlimited; witness: unknown.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&0u64.to_le_bytes());
+        args.push(0x04);
+        args
+    };
+    let tx = build_simple_tx(
+        &mut context,
+        &extra_lock_args,
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::InvalidWitnessLocation as i8,
+    );
+}
+
+#[test]
+fn invalid_predicate() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    let extra_lock_args = build_condition_lock_args(&[5u8; 4], 0x04, &[]);
+    let tx = build_simple_tx(
+        &mut context,
+        &extra_lock_args,
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::InvalidPredicate as i8,
+    );
+}
+
+#[test]
+fn key_is_not_proven() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    // The key is in the tree, but not in the proof.
+    let extra_lock_args = build_condition_lock_args(&[1u8; 4], 0x00, &[]);
+    let tx = build_simple_tx(
+        &mut context,
+        &extra_lock_args,
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::KeyIsNotProven as i8,
+    );
+}
+
+#[test]
+fn predicate_is_not_satisfied() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator);
+    // The key has a value, but it is required to be absent.
+    let extra_lock_args = build_condition_lock_args(&[5u8; 4], 0x01, &[]);
+    let tx = build_simple_tx(
+        &mut context,
+        &extra_lock_args,
+        root.as_slice(),
+        &data_with_proof,
+    );
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::PredicateIsNotSatisfied as i8,
+    );
+}
+
+#[test]
+fn corrupted_proof() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let root = generator.root().to_owned();
+    let data_with_proof = build_data_with_proof(&generator)
+        .as_builder()
+        .proof(Default::default())
+        .build();
+    let tx = build_simple_tx(&mut context, &[], root.as_slice(), &data_with_proof);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputLock(0),
+        VerifyError::ComputeRoot,
+    );
+}
+
+#[test]
+fn mismatched_root() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let generator = build_generator();
+    let data_with_proof = build_data_with_proof(&generator);
+    let tx = build_simple_tx(&mut context, &[], &[1u8; 32], &data_with_proof);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputLock(0),
+        VerifyError::MismatchedRoot,
+    );
+}
+
+// The KV-store cell is updated at block 200, while the proof is against the
+// root at block 100.
+fn build_stale_root_tx(
+    context: &mut Context,
+    extra_lock_args: &[u8],
+    with_new_header: bool,
+) -> TransactionView {
+    let mut generator = build_generator();
+    let stale_data_with_proof = build_data_with_proof(&generator);
+    generator
+        .update(&[5u8; 4], Some(Bytes::copy_from_slice(&[50u8; 10])))
+        .expect("smt update");
+    let root = generator.root().to_owned();
+
+    let old_header = build_header(100);
+    let new_header = build_header(200);
+    context.insert_header(old_header.clone());
+    context.insert_header(new_header.clone());
+
+    let (success_lock, kvstore_type, demo_lock) = build_scripts(context, extra_lock_args);
+    let out_point = create_kvstore_cell(context, &success_lock, &kvstore_type, root.as_slice());
+    context.link_cell_with_block(out_point.clone(), new_header.hash(), 0);
+    let cell_dep = build_cell_dep(out_point);
+    let input = build_input(context, &demo_lock);
+    let output = build_output(&demo_lock);
+    let witness = build_witness(&stale_data_with_proof);
+
+    let mut builder = TransactionBuilder::default()
+        .cell_dep(cell_dep)
+        .header_dep(old_header.hash());
+    if with_new_header {
+        builder = builder.header_dep(new_header.hash());
+    }
+    let tx = builder
+        .input(input)
+        .output(output)
+        .output_data(Default::default())
+        .witness(witness.pack())
+        .build();
+    context.complete_tx(tx)
+}
+
+#[test]
+fn stale_root_with_old_header_deps() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    // Root source: a cell dep; max age: 10 blocks; only the old header is
+    // provided, so the header of the KV-store cell is missing.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&10u64.to_le_bytes());
+        args
+    };
+    let tx = build_stale_root_tx(&mut context, &extra_lock_args, false);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputLock(0),
+        InternalError::HeaderDepNotFound as i8,
+    );
+}
+
+#[test]
+fn stale_root_with_fresh_cell() {
+    utilities::setup();
+
+    let mut context = Context::default();
+
+    // Root source: a cell dep; max age: 10 blocks; the KV-store cell is fresh
+    // enough, but the proof is against its previous root.
+    let extra_lock_args = {
+        let mut args = vec![0x00];
+        args.extend_from_slice(&10u64.to_le_bytes());
+        args
+    };
+    let tx = build_stale_root_tx(&mut context, &extra_lock_args, true);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputLock(0),
+        VerifyError::MismatchedRoot,
+    );
+}
@@ -1,11 +1,50 @@
-use ckb_smt_tool::types::ProofGenerator;
+use ckb_smt_tool::{
+    error::UpdateError,
+    tx_builder::build_create_tx,
+    types::{ProofGenerator, SmtUpdate},
+    unique_id::calculate_unique_id,
+    witness::{smt_update_witness_args, WitnessField},
+};
 use ckb_testtool::{
     builtin::ALWAYS_SUCCESS,
     ckb_types::{bytes::Bytes, core::TransactionBuilder, packed::*, prelude::*},
     context::Context,
 };
+use demo_onchain_kvstore::error::InternalError;
+
+use crate::{
+    contracts::fixtures::{self, KvStoreDeployment},
+    prelude::*,
+    utilities, Loader,
+};
 
-use crate::{prelude::*, utilities, Loader};
+// Deploys the contracts, and returns the deployment and the first input.
+fn prepare(context: &mut Context) -> (KvStoreDeployment, CellInput) {
+    let kvstore = fixtures::deploy_kvstore(context);
+    let input = fixtures::build_input(context, &kvstore.lock_script);
+    (kvstore, input)
+}
+
+// Builds the KV-store cell with the correct unique ID.
+fn build_output(
+    context: &mut Context,
+    kvstore: &KvStoreDeployment,
+    input: &CellInput,
+) -> CellOutput {
+    let unique_id = calculate_unique_id(input.as_slice(), 0);
+    let type_script = kvstore.type_script(context, &unique_id);
+    fixtures::build_kvstore_cell(&kvstore.lock_script, &type_script)
+}
+
+fn build_initial_content() -> SmtUpdate {
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = Bytes::copy_from_slice(&[i; 4]);
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.append_change(k, v);
+    }
+    generator.commit_changes().expect("smt commit")
+}
 
 #[test]
 fn success() {
@@ -36,7 +75,7 @@ fn success() {
 
     // prepare type scripts
     let output_index = 0;
-    let unique_id = utilities::calculate_unique_id(input.clone(), output_index);
+    let unique_id = calculate_unique_id(input.as_slice(), output_index);
     let type_script = context
         .build_script(&type_out_point, Bytes::from(unique_id.to_vec()))
         .expect("type script");
@@ -64,6 +103,125 @@ fn success() {
     let _ = context.should_be_passed_without_limit(&tx);
 }
 
+#[test]
+fn success_with_initial_data() {
+    utilities::setup();
+
+    // deploy contract
+    let mut context = Context::default();
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare lock scripts
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+
+    // prepare inputs
+    let input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let input = CellInput::new_builder()
+        .previous_output(input_out_point)
+        .build();
+
+    // prepare type scripts
+    let output_index = 0;
+    let unique_id = calculate_unique_id(input.as_slice(), output_index);
+    let type_script = context
+        .build_script(&type_out_point, Bytes::from(unique_id.to_vec()))
+        .expect("type script");
+    let type_script_opt = ScriptOpt::new_builder().set(Some(type_script)).build();
+
+    // prepare the initial content
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = Bytes::copy_from_slice(&[i; 4]);
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.append_change(k, v);
+    }
+    let smt_update = generator.commit_changes().expect("smt commit");
+    let root = generator.root();
+
+    // prepare outputs
+    let outputs = vec![CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(lock_script.clone())
+        .type_(type_script_opt.clone())
+        .build()];
+    let outputs_data = vec![Bytes::copy_from_slice(root.as_slice()); outputs.len()];
+
+    // prepare witnesses
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+
+    // build transaction
+    let tx = TransactionBuilder::default()
+        .input(input)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .witness(witness.pack())
+        .build();
+    let tx = context.complete_tx(tx);
+
+    // run
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn success_with_tx_builder() {
+    utilities::setup();
+
+    // deploy contract
+    let mut context = Context::default();
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+    let type_script = context
+        .build_script(&type_out_point, Default::default())
+        .expect("type script");
+
+    // prepare inputs
+    let input_out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    let input = CellInput::new_builder()
+        .previous_output(input_out_point)
+        .build();
+
+    // prepare the initial content
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = Bytes::copy_from_slice(&[i; 4]);
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.append_change(k, v);
+    }
+
+    // build transaction
+    let output = CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(lock_script)
+        .build();
+    let tx = build_create_tx(&mut generator, input, type_script, output).expect("build tx");
+    let tx = context.complete_tx(tx);
+
+    // run
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
 #[test]
 fn no_output_data() {
     utilities::setup();
@@ -93,7 +251,7 @@ fn no_output_data() {
 
     // prepare type scripts
     let output_index = 0;
-    let unique_id = utilities::calculate_unique_id(input.clone(), output_index);
+    let unique_id = calculate_unique_id(input.as_slice(), output_index);
     let type_script = context
         .build_script(&type_out_point, Bytes::from(unique_id.to_vec()))
         .expect("type script");
@@ -116,5 +274,152 @@ fn no_output_data() {
     let tx = context.complete_tx(tx);
 
     // run
-    let _ = context.should_be_failed_without_limit(&tx);
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::CreateInitializedDataInvalidLength as i8,
+    );
+}
+
+#[test]
+fn unknown_operation() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let output = build_output(&mut context, &kvstore, &input);
+    let outputs = vec![(output, Bytes::from(vec![0u8; 32])); 2];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::UnknownOperation as i8,
+    );
+}
+
+#[test]
+fn invalid_args_length() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let unique_id = calculate_unique_id(input.as_slice(), 0);
+    let type_script = kvstore.type_script(&mut context, &unique_id[..31]);
+    let output = fixtures::build_kvstore_cell(&kvstore.lock_script, &type_script);
+    let outputs = vec![(output, Bytes::from(vec![0u8; 32]))];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::CreateInvalidArgsLength as i8,
+    );
+}
+
+#[test]
+fn incorrect_unique_id() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    // The unique ID for the output at index 1.
+    let unique_id = calculate_unique_id(input.as_slice(), 1);
+    let type_script = kvstore.type_script(&mut context, &unique_id);
+    let output = fixtures::build_kvstore_cell(&kvstore.lock_script, &type_script);
+    let outputs = vec![(output, Bytes::from(vec![0u8; 32]))];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::CreateIncorrectUniqueId as i8,
+    );
+}
+
+#[test]
+fn initialized_data_not_empty() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let output = build_output(&mut context, &kvstore, &input);
+    let outputs = vec![(output, Bytes::from(vec![1u8; 32]))];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::CreateInitializedDataNotEmpty as i8,
+    );
+}
+
+#[test]
+fn initial_content_with_invalid_encoding() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let output = build_output(&mut context, &kvstore, &input);
+    let outputs = vec![(output, Bytes::from(vec![1u8; 32]))];
+    let witness = WitnessField::OutputType
+        .set(WitnessArgs::default(), &[0xff; 16])
+        .as_bytes();
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::Encoding as i8,
+    );
+}
+
+#[test]
+fn initial_content_with_mismatched_new_root() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let output = build_output(&mut context, &kvstore, &input);
+    let outputs = vec![(output, Bytes::from(vec![1u8; 32]))];
+    let smt_update = build_initial_content();
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::OutputType(0),
+        InternalError::CreateNewRootIsMismatch as i8,
+    );
+}
+
+#[test]
+fn initial_content_from_non_empty_tree() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (kvstore, input) = prepare(&mut context);
+    let output = build_output(&mut context, &kvstore, &input);
+
+    // The initial content is an update on a non-empty tree.
+    let mut generator = ProofGenerator::new();
+    generator
+        .update(&[1u8; 4], Some(Bytes::copy_from_slice(&[1u8; 10])))
+        .expect("smt update");
+    generator.append_change(
+        Bytes::copy_from_slice(&[2u8; 4]),
+        Some(Bytes::copy_from_slice(&[2u8; 10])),
+    );
+    let smt_update = generator.commit_changes().expect("smt commit");
+    let root = Bytes::copy_from_slice(generator.root().as_slice());
+
+    let outputs = vec![(output, root)];
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::OutputType(0),
+        UpdateError::MismatchedOldRoot,
+    );
 }
@@ -1,3 +1,4 @@
+use ckb_smt_tool::tx_builder::build_destroy_tx;
 use ckb_testtool::{
     builtin::ALWAYS_SUCCESS,
     ckb_types::{bytes::Bytes, core::TransactionBuilder, packed::*, prelude::*},
@@ -56,3 +57,38 @@ fn success() {
     // run
     let _ = context.should_be_passed_without_limit(&tx);
 }
+
+#[test]
+fn success_with_tx_builder() {
+    utilities::setup();
+
+    // deploy contract
+    let mut context = Context::default();
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+    let type_script = context
+        .build_script(&type_out_point, Bytes::from([0u8; 32].to_vec()))
+        .expect("type script");
+    let type_script_opt = ScriptOpt::new_builder().set(Some(type_script)).build();
+
+    // prepare the KV-store cell
+    let cell = CellOutput::new_builder()
+        .capacity(1000u64.pack())
+        .lock(lock_script)
+        .type_(type_script_opt)
+        .build();
+    let out_point = context.create_cell(cell.clone(), Bytes::new());
+
+    // build transaction
+    let tx = build_destroy_tx(cell, out_point);
+    let tx = context.complete_tx(tx);
+
+    // run
+    let _ = context.should_be_passed_without_limit(&tx);
+}
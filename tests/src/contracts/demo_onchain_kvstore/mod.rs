@@ -1,3 +1,10 @@
 mod create;
 mod destroy;
 mod update;
+
+// Following exit codes of the contract are unreachable, so they are not tested:
+// - `ItemMissing`, `LengthNotEnough` and `Unknown`: the contract only loads
+//   whole items which exist.
+// - `UpdateError::ComputeNewRoot`: the new root is computed with the same keys
+//   and the same proof as the old root, so it never fails if the old root is
+//   computed.
@@ -1,11 +1,41 @@
-use ckb_smt_tool::types::ProofGenerator;
+use ckb_smt_tool::{
+    error::UpdateError,
+    tx_builder::build_update_tx,
+    types::{ProofGenerator, SmtUpdate},
+    witness::{smt_update_witness_args, WitnessField},
+};
 use ckb_testtool::{
     builtin::ALWAYS_SUCCESS,
     ckb_types::{bytes::Bytes, core::TransactionBuilder, packed::*, prelude::*},
     context::Context,
 };
+use demo_onchain_kvstore::error::InternalError;
+
+use crate::{contracts::fixtures, prelude::*, utilities, Loader};
+
+// Returns the old root, the update and the new root.
+fn build_update(new_value: &[u8]) -> (Bytes, SmtUpdate, Bytes) {
+    let mut generator = fixtures::build_generator();
+    let old_root = Bytes::copy_from_slice(generator.root().as_slice());
+    generator.append_change(
+        Bytes::copy_from_slice(&[15u8; 4]),
+        Some(Bytes::copy_from_slice(new_value)),
+    );
+    let smt_update = generator.commit_changes().expect("smt commit");
+    let new_root = Bytes::copy_from_slice(generator.root().as_slice());
+    (old_root, smt_update, new_root)
+}
 
-use crate::{prelude::*, utilities, Loader};
+// Deploys the contracts, creates the KV-store cell with the data, and returns
+// the input and the output for the KV-store cell.
+fn prepare(context: &mut Context, input_data: Bytes) -> (CellInput, CellOutput) {
+    let kvstore = fixtures::deploy_kvstore(context);
+    let type_script = kvstore.type_script(context, &[0u8; 32]);
+    let cell = fixtures::build_kvstore_cell(&kvstore.lock_script, &type_script);
+    let out_point = context.create_cell(cell.clone(), input_data);
+    let input = CellInput::new_builder().previous_output(out_point).build();
+    (input, cell)
+}
 
 #[test]
 fn success() {
@@ -74,13 +104,7 @@ fn success() {
     let outputs_data = vec![Bytes::copy_from_slice(new_root.as_slice()); outputs.len()];
 
     // prepare witnesses
-    let witness = {
-        let type_args = BytesOpt::new_builder()
-            .set(Some(smt_update.as_slice().pack()))
-            .build();
-        let witness_args = WitnessArgs::new_builder().output_type(type_args).build();
-        witness_args.as_bytes()
-    };
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
 
     // build transaction
     let tx = TransactionBuilder::default()
@@ -94,3 +118,227 @@ fn success() {
     // run
     let _ = context.should_be_passed_without_limit(&tx);
 }
+
+#[test]
+fn success_with_tx_builder() {
+    utilities::setup();
+
+    let mut generator = ProofGenerator::new();
+    {
+        for i in 0..20u8 {
+            let k = [i; 4];
+            let v = Some(Bytes::copy_from_slice(&[i; 10]));
+            generator.update(&k, v).expect("smt update");
+        }
+    }
+    let old_root = generator.root().to_owned();
+    {
+        generator.append_change(Bytes::copy_from_slice(&[10u8; 4]), None);
+        generator.append_change(
+            Bytes::copy_from_slice(&[25u8; 4]),
+            Some(Bytes::copy_from_slice(&[25; 20])),
+        );
+    }
+
+    // deploy contract
+    let mut context = Context::default();
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+
+    // prepare scripts
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+    let type_script = context
+        .build_script(&type_out_point, Bytes::from([0u8; 32].to_vec()))
+        .expect("type script");
+    let type_script_opt = ScriptOpt::new_builder().set(Some(type_script)).build();
+
+    // prepare the KV-store cell
+    let cell = CellOutput::new_builder()
+        .capacity(1000u64.pack())
+        .lock(lock_script)
+        .type_(type_script_opt)
+        .build();
+    let cell_data = Bytes::copy_from_slice(old_root.as_slice());
+    let out_point = context.create_cell(cell.clone(), cell_data.clone());
+
+    // build transaction
+    let tx = build_update_tx(&mut generator, cell, &cell_data, out_point).expect("build tx");
+    let tx = context.complete_tx(tx);
+
+    // run
+    let _ = context.should_be_passed_without_limit(&tx);
+}
+
+#[test]
+fn input_data_invalid_length() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, smt_update, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root.slice(..31));
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::UpdateInputDataInvalidLength as i8,
+    );
+}
+
+#[test]
+fn output_data_invalid_length() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, smt_update, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let outputs = vec![(output, new_root.slice(..31))];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::UpdateOutputDataInvalidLength as i8,
+    );
+}
+
+#[test]
+fn no_witness() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, _, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::IndexOutOfBound as i8,
+    );
+}
+
+#[test]
+fn witness_is_not_existed() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, _, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let witness = WitnessArgs::default().as_bytes();
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::UpdateWitnessIsNotExisted as i8,
+    );
+}
+
+#[test]
+fn witness_with_invalid_encoding() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, _, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let witness = WitnessField::OutputType
+        .set(WitnessArgs::default(), &[0xff; 16])
+        .as_bytes();
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::Encoding as i8,
+    );
+}
+
+#[test]
+fn new_root_is_mismatch() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, smt_update, _) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let output_data = Bytes::from(vec![1u8; 32]);
+    let outputs = vec![(output, output_data)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_code(
+        &tx,
+        FailedScript::InputType(0),
+        InternalError::UpdateNewRootIsMismatch as i8,
+    );
+}
+
+#[test]
+fn corrupted_proof() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, smt_update, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let smt_update = smt_update.as_builder().proof(Default::default()).build();
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputType(0),
+        UpdateError::ComputeOldRoot,
+    );
+}
+
+#[test]
+fn mismatched_old_root() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (_, smt_update, new_root) = build_update(&[15u8; 20]);
+    let (input, output) = prepare(&mut context, Bytes::from(vec![1u8; 32]));
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let outputs = vec![(output, new_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputType(0),
+        UpdateError::MismatchedOldRoot,
+    );
+}
+
+#[test]
+fn mismatched_new_root() {
+    utilities::setup();
+
+    let mut context = Context::default();
+    let (old_root, smt_update, _) = build_update(&[15u8; 20]);
+    // Claims the root of another update on the same keys.
+    let (_, other_update, other_root) = build_update(&[16u8; 20]);
+    let (input, output) = prepare(&mut context, old_root);
+    let smt_update = smt_update
+        .as_builder()
+        .new_root(other_update.new_root())
+        .build();
+    let witness = smt_update_witness_args(WitnessField::OutputType, &smt_update).as_bytes();
+    let outputs = vec![(output, other_root)];
+    let tx = fixtures::build_tx(&mut context, vec![], vec![input], outputs, vec![witness]);
+
+    context.should_be_failed_with_smt_error(
+        &tx,
+        FailedScript::InputType(0),
+        UpdateError::MismatchedNewRoot,
+    );
+}
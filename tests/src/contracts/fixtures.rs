@@ -0,0 +1,116 @@
+//! Fixtures which are shared by the tests of contracts.
+
+use ckb_smt_tool::types::ProofGenerator;
+use ckb_testtool::{
+    builtin::ALWAYS_SUCCESS,
+    ckb_types::{
+        bytes::Bytes,
+        core::{TransactionBuilder, TransactionView},
+        packed::*,
+        prelude::*,
+    },
+    context::Context,
+};
+
+use crate::Loader;
+
+/// The deployed KV-store contract, and a lock script which always success.
+pub(super) struct KvStoreDeployment {
+    pub(super) lock_script: Script,
+    pub(super) type_out_point: OutPoint,
+}
+
+impl KvStoreDeployment {
+    /// Builds the type script of a KV-store cell.
+    pub(super) fn type_script(&self, context: &mut Context, args: &[u8]) -> Script {
+        context
+            .build_script(&self.type_out_point, Bytes::copy_from_slice(args))
+            .expect("type script")
+    }
+}
+
+/// Deploys the KV-store contract and the lock which always success.
+pub(super) fn deploy_kvstore(context: &mut Context) -> KvStoreDeployment {
+    let contract_bin: Bytes = Loader::default().load_binary("demo-onchain-kvstore");
+    let type_out_point = context.deploy_cell(contract_bin);
+    let lock_out_point = context.deploy_cell(ALWAYS_SUCCESS.clone());
+    let lock_script = context
+        .build_script(&lock_out_point, Default::default())
+        .expect("lock script");
+    KvStoreDeployment {
+        lock_script,
+        type_out_point,
+    }
+}
+
+/// Returns a generator with 20 leaves, the key `[i; 4]` has the value
+/// `[i; 10]`.
+pub(super) fn build_generator() -> ProofGenerator {
+    let mut generator = ProofGenerator::new();
+    for i in 0..20u8 {
+        let k = [i; 4];
+        let v = Some(Bytes::copy_from_slice(&[i; 10]));
+        generator.update(&k, v).expect("smt update");
+    }
+    generator
+}
+
+/// Builds a KV-store cell, without creating it.
+pub(super) fn build_kvstore_cell(lock_script: &Script, type_script: &Script) -> CellOutput {
+    CellOutput::new_builder()
+        .capacity(1000u64.pack())
+        .lock(lock_script.clone())
+        .type_(Some(type_script.clone()).pack())
+        .build()
+}
+
+/// Creates a live KV-store cell which stores the root.
+pub(super) fn create_kvstore_cell(
+    context: &mut Context,
+    lock_script: &Script,
+    type_script: &Script,
+    root: &[u8],
+) -> OutPoint {
+    let cell = build_kvstore_cell(lock_script, type_script);
+    context.create_cell(cell, Bytes::copy_from_slice(root))
+}
+
+/// Creates a live cell without type script and data, and returns it as an
+/// input.
+pub(super) fn build_input(context: &mut Context, lock_script: &Script) -> CellInput {
+    let out_point = context.create_cell(
+        CellOutput::new_builder()
+            .capacity(1000u64.pack())
+            .lock(lock_script.clone())
+            .build(),
+        Bytes::new(),
+    );
+    CellInput::new_builder().previous_output(out_point).build()
+}
+
+/// Builds an output without type script.
+pub(super) fn build_output(lock_script: &Script) -> CellOutput {
+    CellOutput::new_builder()
+        .capacity(500u64.pack())
+        .lock(lock_script.clone())
+        .build()
+}
+
+/// Builds a transaction, and completes its cell deps by the context.
+pub(super) fn build_tx(
+    context: &mut Context,
+    cell_deps: Vec<CellDep>,
+    inputs: Vec<CellInput>,
+    outputs: Vec<(CellOutput, Bytes)>,
+    witnesses: Vec<Bytes>,
+) -> TransactionView {
+    let (outputs, outputs_data): (Vec<_>, Vec<_>) = outputs.into_iter().unzip();
+    let tx = TransactionBuilder::default()
+        .cell_deps(cell_deps)
+        .inputs(inputs)
+        .outputs(outputs)
+        .outputs_data(outputs_data.pack())
+        .witnesses(witnesses.pack())
+        .build();
+    context.complete_tx(tx)
+}
@@ -1,2 +1,3 @@
 mod demo_check_data;
 mod demo_onchain_kvstore;
+mod fixtures;
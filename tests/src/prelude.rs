@@ -1,11 +1,54 @@
-use std::env;
+use std::{env, fmt};
 
 use ckb_testtool::{
     ckb_error::Error,
+    ckb_script::{ScriptError, TransactionScriptError},
     ckb_types::core::{Cycle, TransactionView},
     context::Context,
 };
 
+/// The script group which fails a transaction, located by the first index of
+/// its cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedScript {
+    InputLock(usize),
+    InputType(usize),
+    OutputType(usize),
+}
+
+/// Errors from `ckb-smt-tool` are returned by contracts as `0x60 + error`.
+const SMT_TOOL_ERROR_OFFSET: i8 = 0x60;
+
+impl FailedScript {
+    // Parses the source of a `TransactionScriptError`, e.g. "Inputs[0].Lock".
+    fn parse(source: &str) -> Option<Self> {
+        let (cells, group) = source.split_once('.')?;
+        let (cells, index) = cells.strip_suffix(']')?.split_once('[')?;
+        let index = index.parse().ok()?;
+        match (cells, group) {
+            ("Inputs", "Lock") => Some(Self::InputLock(index)),
+            ("Inputs", "Type") => Some(Self::InputType(index)),
+            ("Outputs", "Type") => Some(Self::OutputType(index)),
+            _ => None,
+        }
+    }
+}
+
+// Returns the script group which fails the transaction, and its exit code.
+fn script_failure(err: &Error) -> (FailedScript, i8) {
+    let script_error = err
+        .downcast_ref::<TransactionScriptError>()
+        .unwrap_or_else(|| panic!("should be failed by a script, but failed since {err}"));
+    let code = match script_error.script_error() {
+        ScriptError::ValidationFailure(_, code) => *code,
+        _ => panic!("should be failed with an exit code, but failed since {err}"),
+    };
+    let source = script_error.originating_script().to_string();
+    let failed_script = FailedScript::parse(&source)
+        .unwrap_or_else(|| panic!("unknown script group {source} in {err}"));
+    (failed_script, code)
+}
+
 // This helper method runs Context::verify_tx, but in case error happens,
 // it also dumps current transaction to failed_txs folder.
 pub trait ContextExt {
@@ -18,6 +61,44 @@ pub trait ContextExt {
     fn should_be_failed_without_limit(&self, tx: &TransactionView) -> Result<Cycle, Error> {
         self.should_be_failed(tx, Cycle::MAX)
     }
+
+    /// Asserts that the transaction is failed by the script group, with the
+    /// exit code.
+    fn should_be_failed_with_code(&self, tx: &TransactionView, script: FailedScript, code: i8) {
+        let err = self
+            .should_be_failed_without_limit(tx)
+            .expect_err("should be failed");
+        let (actual_script, actual_code) = script_failure(&err);
+        assert_eq!(
+            (actual_script, actual_code),
+            (script, code),
+            "should be failed by {script:?} with exit code {code:#04x}, but failed since {err}"
+        );
+    }
+
+    /// Asserts that the transaction is failed by the script group, with the
+    /// error from `ckb-smt-tool`.
+    fn should_be_failed_with_smt_error<E>(
+        &self,
+        tx: &TransactionView,
+        script: FailedScript,
+        error: E,
+    ) where
+        E: TryFrom<i8> + fmt::Debug + PartialEq,
+    {
+        let err = self
+            .should_be_failed_without_limit(tx)
+            .expect_err("should be failed");
+        let (actual_script, actual_code) = script_failure(&err);
+        let actual_error = actual_code
+            .checked_sub(SMT_TOOL_ERROR_OFFSET)
+            .and_then(|code| E::try_from(code).ok());
+        assert_eq!(
+            (actual_script, actual_error.as_ref()),
+            (script, Some(&error)),
+            "should be failed by {script:?} with {error:?}, but failed since {err}"
+        );
+    }
 }
 
 impl ContextExt for Context {
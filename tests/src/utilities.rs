@@ -1,9 +1,5 @@
 //! Utilities for tests only.
 
-use ckb_testtool::{
-    ckb_hash::{new_blake2b, BLAKE2B_LEN},
-    ckb_types::{packed, prelude::*},
-};
 use env_logger::{Builder, Target};
 use log::LevelFilter;
 
@@ -16,15 +12,3 @@ pub(crate) fn setup() {
         .try_init();
     println!();
 }
-
-pub(crate) fn calculate_unique_id(
-    input: packed::CellInput,
-    output_index: usize,
-) -> [u8; BLAKE2B_LEN] {
-    let mut blake2b = new_blake2b();
-    blake2b.update(input.as_slice());
-    blake2b.update(&(output_index as u64).to_le_bytes());
-    let mut ret = [0; BLAKE2B_LEN];
-    blake2b.finalize(&mut ret);
-    ret
-}